@@ -0,0 +1,18 @@
+//! Exposes the importer-lib version locked in Cargo.lock as IMPORTER_LIB_VERSION, so that results
+//! cached by a build with another version are not reused.
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let version = lock
+        .split("[[package]]")
+        .find(|package| package.contains("name = \"importer-lib\""))
+        .and_then(|package| {
+            package
+                .lines()
+                .find_map(|line| line.strip_prefix("version = \""))
+        })
+        .map(|version| version.trim_end_matches('"'))
+        .unwrap_or("unknown");
+    println!("cargo:rustc-env=IMPORTER_LIB_VERSION={version}");
+}
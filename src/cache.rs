@@ -0,0 +1,238 @@
+use importer_lib::anyhow;
+use importer_lib::serde_json::{self, Value};
+use importer_lib::utils::get_local_datetime_with_format;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default directory (relative to the working directory, like the error log) for cached results
+pub const VALIDATION_CACHE_DIR: &str = ".dkan-importer-cache";
+
+/// Versions of this tool and of importer-lib, part of every validation cache key: an upgrade
+/// may change how files are validated and exported
+pub const BUILD_VERSIONS: &str = concat!(
+    "dkan-importer ",
+    env!("CARGO_PKG_VERSION"),
+    ", importer-lib ",
+    env!("IMPORTER_LIB_VERSION")
+);
+
+/// How long cached validation results are used before they expire, the default of --cache-ttl-days
+pub const DEFAULT_VALIDATION_CACHE_TTL_DAYS: u64 = 7;
+
+/// Outcome of a validation run, stored so that re-running on the exact same schema, file and
+/// sheet can report it without parsing the workbook again. Only the error count is kept, never
+/// the rows or the error log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedValidation {
+    /// 0 when validation passed
+    pub error_count: usize,
+    pub created_at: String,
+    /// Seconds since the UNIX epoch, for expiry
+    pub stored_at: u64,
+}
+
+impl CachedValidation {
+    pub fn new(error_count: usize) -> Self {
+        CachedValidation {
+            error_count,
+            created_at: get_local_datetime_with_format("%Y-%m-%d %H:%M:%S"),
+            stored_at: seconds_since_epoch(SystemTime::now()),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.error_count == 0
+    }
+}
+
+fn seconds_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// On-disk cache of validation results keyed on (schema, header mapping, Excel file content,
+/// sheet name). Entries expire after `ttl` and expired ones are deleted whenever a result is
+/// stored. A passing run can be cached together with a copy of its exported CSV (the data to be
+/// uploaded) so that the export can be reused; only --cache-exports does that.
+pub struct ValidationCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl ValidationCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        ValidationCache {
+            dir: dir.into(),
+            ttl: Duration::from_secs(DEFAULT_VALIDATION_CACHE_TTL_DAYS * 24 * 60 * 60),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Cache key for a validation run by this build. Any change to the schema, the
+    /// title-to-name mapping (the headers of the exported CSV), the workbook bytes, the sheet
+    /// name or the `BUILD_VERSIONS` produces a different key.
+    pub fn key(
+        json_schema: &Value,
+        title_to_name_mapping: &HashMap<String, String>,
+        excel_content: &[u8],
+        sheet_name: &str,
+    ) -> String {
+        Self::key_for_versions(
+            BUILD_VERSIONS,
+            json_schema,
+            title_to_name_mapping,
+            excel_content,
+            sheet_name,
+        )
+    }
+
+    /// Cache key for a validation run by the given tool and importer-lib `versions`: the SHA-256
+    /// of every input, each prefixed with its length so that no two inputs run together
+    pub fn key_for_versions(
+        versions: &str,
+        json_schema: &Value,
+        title_to_name_mapping: &HashMap<String, String>,
+        excel_content: &[u8],
+        sheet_name: &str,
+    ) -> String {
+        let mut mapping: Vec<(&String, &String)> = title_to_name_mapping.iter().collect();
+        mapping.sort();
+        let mut hasher = Sha256::new();
+        let mut update = |bytes: &[u8]| {
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(bytes);
+        };
+        update(versions.as_bytes());
+        update(json_schema.to_string().as_bytes());
+        for (title, name) in mapping {
+            update(title.as_bytes());
+            update(name.as_bytes());
+        }
+        update(excel_content);
+        update(sheet_name.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// The cached result, `None` when there is none or it has expired
+    pub fn get(&self, key: &str) -> Option<CachedValidation> {
+        let content = std::fs::read_to_string(self.entry_path(key)).ok()?;
+        let cached: CachedValidation = serde_json::from_str(&content).ok()?;
+        if self.is_expired(&cached) {
+            self.remove(key);
+            return None;
+        }
+        // A passing result is only useful with its export
+        if cached.passed() && !self.exported_csv_path(key).exists() {
+            return None;
+        }
+        Some(cached)
+    }
+
+    /// Store a failed validation
+    pub fn store(&self, key: &str, result: &CachedValidation) -> Result<(), anyhow::Error> {
+        std::fs::create_dir_all(&self.dir)?;
+        self.evict_expired()?;
+        std::fs::write(self.entry_path(key), serde_json::to_string_pretty(result)?)?;
+        return Ok(());
+    }
+
+    /// Store a passing validation together with a copy of the CSV it was exported to
+    pub fn store_passed(&self, key: &str, csv_path: &str) -> Result<(), anyhow::Error> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::copy(csv_path, self.exported_csv_path(key))?;
+        self.store(key, &CachedValidation::new(0))
+    }
+
+    /// Copy the export of a cached passing run to `csv_path`
+    pub fn restore_export(&self, key: &str, csv_path: &str) -> Result<(), anyhow::Error> {
+        std::fs::copy(self.exported_csv_path(key), csv_path)?;
+        return Ok(());
+    }
+
+    /// Delete the expired (or unreadable) entries, returning how many were deleted
+    pub fn evict_expired(&self) -> Result<usize, anyhow::Error> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Ok(0);
+        };
+        let mut evicted = 0;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let expired = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<CachedValidation>(&content).ok())
+                .is_none_or(|cached| self.is_expired(&cached));
+            if let (true, Some(key)) = (expired, path.file_stem().and_then(|s| s.to_str())) {
+                self.remove(key);
+                evicted += 1;
+            }
+        }
+        return Ok(evicted);
+    }
+
+    fn is_expired(&self, cached: &CachedValidation) -> bool {
+        let age = seconds_since_epoch(SystemTime::now()).saturating_sub(cached.stored_at);
+        age >= self.ttl.as_secs()
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = std::fs::remove_file(self.entry_path(key));
+        let _ = std::fs::remove_file(self.exported_csv_path(key));
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn exported_csv_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.csv"))
+    }
+}
+
+/// Default directory for cached data dictionary responses
+pub const SCHEMA_CACHE_DIR: &str = ".dkan-importer-cache/schemas";
+
+/// Body of a previous response together with the ETag the server sent for it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedResponse {
+    pub etag: String,
+    pub body: String,
+}
+
+/// On-disk cache of data dictionary responses keyed on the request URL, revalidated with
+/// `If-None-Match` so an unchanged dictionary is not downloaded again
+pub struct SchemaCache {
+    dir: PathBuf,
+}
+
+impl SchemaCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        SchemaCache { dir: dir.into() }
+    }
+
+    pub fn get(&self, url: &str) -> Option<CachedResponse> {
+        let content = std::fs::read_to_string(self.entry_path(url)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn store(&self, url: &str, response: &CachedResponse) -> Result<(), anyhow::Error> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.entry_path(url), serde_json::to_string(response)?)?;
+        return Ok(());
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:x}.json", Sha256::digest(url.as_bytes())))
+    }
+}
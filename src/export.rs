@@ -0,0 +1,843 @@
+use importer_lib::anyhow;
+use importer_lib::serde_json::Value;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Line terminator of the uploaded CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineTerminator {
+    Lf,
+    Crlf,
+}
+
+impl FromStr for LineTerminator {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "lf" => Ok(LineTerminator::Lf),
+            "crlf" => Ok(LineTerminator::Crlf),
+            _ => Err(anyhow::anyhow!(
+                "Unsupported line terminator '{value}'. Supported: lf, crlf"
+            )),
+        }
+    }
+}
+
+impl LineTerminator {
+    /// Terminator of the first line of the file at `csv_path`, LF when it has a single line
+    fn of_file(csv_path: &str) -> Result<Self, anyhow::Error> {
+        let mut first_line = Vec::new();
+        std::io::BufReader::new(std::fs::File::open(csv_path)?)
+            .read_until(b'\n', &mut first_line)?;
+        if first_line.ends_with(b"\r\n") {
+            return Ok(LineTerminator::Crlf);
+        }
+        return Ok(LineTerminator::Lf);
+    }
+
+    fn to_csv(self) -> csv::Terminator {
+        match self {
+            LineTerminator::Lf => csv::Terminator::Any(b'\n'),
+            LineTerminator::Crlf => csv::Terminator::CRLF,
+        }
+    }
+}
+
+/// Post-processing applied to the CSV written by `ExcelValidator::export_to_csv` before it is uploaded.
+/// The default leaves the file untouched.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Replace line breaks inside cell values with this separator
+    pub flatten_newlines: Option<String>,
+    /// Keep only these columns (CSV header names), in their original order
+    pub columns: Option<Vec<String>>,
+    /// Constant (column, value) pairs appended to every row, e.g. for lineage
+    pub constants: Vec<(String, String)>,
+    /// Enum members per column (CSV header name). Values matching a member case-insensitively
+    /// are written with the member's canonical casing.
+    pub canonical_enums: HashMap<String, Vec<String>>,
+    /// Boolean columns (CSV header names) written as 1/0 instead of true/false
+    pub integer_booleans: Vec<String>,
+    /// UUID columns (CSV header names) written in lowercase
+    pub uuid_columns: Vec<String>,
+    /// Rewrite the file with this line terminator. Without it, a rewritten file keeps the
+    /// terminator it was written with.
+    pub line_terminator: Option<LineTerminator>,
+}
+
+impl ExportOptions {
+    /// True when no option is set, so the exported file can be uploaded as-is
+    pub fn is_noop(&self) -> bool {
+        self.flatten_newlines.is_none()
+            && self.columns.is_none()
+            && self.constants.is_empty()
+            && self.canonical_enums.is_empty()
+            && self.line_terminator.is_none()
+            && self.integer_booleans.is_empty()
+            && self.uuid_columns.is_empty()
+    }
+
+    /// Rewrite the CSV at `csv_path` in place with the configured options. The rows are streamed
+    /// to a temporary file that replaces the CSV only when a column, a value or the line
+    /// terminator changed, so an export the options don't affect keeps its bytes.
+    pub fn apply(&self, csv_path: &str) -> Result<(), anyhow::Error> {
+        if self.is_noop() {
+            return Ok(());
+        }
+
+        let temp_path = format!("{csv_path}.tmp");
+        match self.rewrite(csv_path, &temp_path) {
+            Ok(true) => std::fs::rename(&temp_path, csv_path)?,
+            Ok(false) => std::fs::remove_file(&temp_path)?,
+            Err(e) => {
+                let _ = std::fs::remove_file(&temp_path);
+                return Err(e);
+            }
+        }
+        return Ok(());
+    }
+
+    /// Write the processed CSV to `temp_path`, returning whether it differs from the source
+    fn rewrite(&self, csv_path: &str, temp_path: &str) -> Result<bool, anyhow::Error> {
+        let mut reader = csv::Reader::from_path(csv_path)?;
+        let headers = reader.headers()?.clone();
+        let kept_indices: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| match &self.columns {
+                Some(columns) => columns.iter().any(|column| column == header),
+                None => true,
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let source_terminator = LineTerminator::of_file(csv_path)?;
+        let terminator = self.line_terminator.unwrap_or(source_terminator);
+        let mut changed = terminator != source_terminator
+            || kept_indices.len() != headers.len()
+            || !self.constants.is_empty();
+
+        let mut writer = csv::WriterBuilder::new()
+            .terminator(terminator.to_csv())
+            .from_path(temp_path)?;
+        writer.write_record(
+            kept_indices
+                .iter()
+                .map(|&index| &headers[index])
+                .chain(self.constants.iter().map(|(column, _)| column.as_str())),
+        )?;
+        for record in reader.records() {
+            let record = record?;
+            let mut values = Vec::with_capacity(kept_indices.len() + self.constants.len());
+            for &index in &kept_indices {
+                let value = record.get(index).unwrap_or_default();
+                let processed = self.process_value(&headers[index], value);
+                changed |= processed != value;
+                values.push(processed);
+            }
+            values.extend(self.constants.iter().map(|(_, value)| value.clone()));
+            writer.write_record(&values)?;
+        }
+        writer
+            .flush()
+            .map_err(|e| anyhow::anyhow!("Failed to write CSV {temp_path}: {e}"))?;
+        return Ok(changed);
+    }
+
+    fn process_value(&self, column: &str, value: &str) -> String {
+        let value = match &self.flatten_newlines {
+            Some(separator) => flatten_newlines(value, separator),
+            None => value.to_string(),
+        };
+        if self.integer_booleans.iter().any(|c| c == column) {
+            return boolean_as_integer(&value).unwrap_or(value);
+        }
+        if self.uuid_columns.iter().any(|c| c == column) {
+            return value.to_lowercase();
+        }
+        match self.canonical_enums.get(column) {
+            Some(members) => canonical_enum_value(members, &value).unwrap_or(value),
+            None => value,
+        }
+    }
+}
+
+/// "1" for true and "0" for false (ignoring case), `None` for anything else such as empty cells
+pub fn boolean_as_integer(value: &str) -> Option<String> {
+    match value.to_lowercase().as_str() {
+        "true" => Some("1".to_string()),
+        "false" => Some("0".to_string()),
+        _ => None,
+    }
+}
+
+/// The enum member equal to `value` ignoring case, if any
+pub fn canonical_enum_value(members: &[String], value: &str) -> Option<String> {
+    let lowercase_value = value.to_lowercase();
+    members
+        .iter()
+        .find(|member| member.to_lowercase() == lowercase_value)
+        .cloned()
+}
+
+/// Parse a `column=value` constant column. The value may be empty or contain '='.
+pub fn parse_constant_column(definition: &str) -> Result<(String, String), String> {
+    let (column, value) = definition
+        .split_once('=')
+        .ok_or_else(|| format!("Expected COLUMN=VALUE, got '{definition}'"))?;
+    let column = column.trim();
+    if column.is_empty() {
+        return Err(format!("Missing column name in '{definition}'"));
+    }
+    return Ok((column.to_string(), value.to_string()));
+}
+
+/// Expected number of data rows, checked before upload as a guard against truncated files
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RowCountExpectation {
+    pub exact: Option<usize>,
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+impl RowCountExpectation {
+    pub fn check(&self, row_count: usize) -> Result<(), anyhow::Error> {
+        if let Some(exact) = self.exact {
+            if row_count != exact {
+                return Err(anyhow::anyhow!(
+                    "Expected exactly {exact} data rows but the file has {row_count}"
+                ));
+            }
+        }
+        if let Some(min) = self.min {
+            if row_count < min {
+                return Err(anyhow::anyhow!(
+                    "Expected at least {min} data rows but the file has {row_count}"
+                ));
+            }
+        }
+        if let Some(max) = self.max {
+            if row_count > max {
+                return Err(anyhow::anyhow!(
+                    "Expected at most {max} data rows but the file has {row_count}"
+                ));
+            }
+        }
+        return Ok(());
+    }
+}
+
+/// Columns of the CSV holding the same non-empty value on every row (with at least two rows),
+/// often a template placeholder that was never filled in. Returns (column, value) pairs.
+pub fn find_constant_columns(csv_path: &str) -> Result<Vec<(String, String)>, anyhow::Error> {
+    let mut reader = csv::Reader::from_path(csv_path)?;
+    let headers = reader.headers()?.clone();
+    let mut first_values: Option<Vec<String>> = None;
+    let mut constant = vec![true; headers.len()];
+    let mut row_count = 0;
+
+    for record in reader.records() {
+        let record = record?;
+        row_count += 1;
+        match &first_values {
+            None => first_values = Some(record.iter().map(|v| v.to_string()).collect()),
+            Some(first) => {
+                for (index, value) in record.iter().enumerate() {
+                    if first.get(index).map(|f| f.as_str()) != Some(value) {
+                        constant[index] = false;
+                    }
+                }
+            }
+        }
+    }
+
+    let (Some(first_values), true) = (first_values, row_count >= 2) else {
+        return Ok(Vec::new());
+    };
+    let constant_columns = headers
+        .iter()
+        .zip(first_values)
+        .zip(constant)
+        .filter(|((_, value), is_constant)| *is_constant && !value.is_empty())
+        .map(|((header, value), _)| (header.to_string(), value))
+        .collect();
+    return Ok(constant_columns);
+}
+
+/// Remove the given columns from the CSV at `csv_path`, in place. Pass the `line_terminator`
+/// of the export options so a file already rewritten with them keeps its line endings.
+pub fn drop_csv_columns(
+    csv_path: &str,
+    dropped: &[String],
+    line_terminator: Option<LineTerminator>,
+) -> Result<(), anyhow::Error> {
+    let headers = csv::Reader::from_path(csv_path)?.headers()?.clone();
+    let kept = headers
+        .iter()
+        .filter(|header| !dropped.iter().any(|column| column == header))
+        .map(|header| header.to_string())
+        .collect();
+    ExportOptions {
+        columns: Some(kept),
+        line_terminator,
+        ..Default::default()
+    }
+    .apply(csv_path)
+}
+
+/// Number of data rows (excluding the header) in a CSV file
+pub fn count_csv_rows(csv_path: &str) -> Result<usize, anyhow::Error> {
+    let mut reader = csv::Reader::from_path(csv_path)?;
+    let mut count = 0;
+    for record in reader.records() {
+        record?;
+        count += 1;
+    }
+    return Ok(count);
+}
+
+/// Replace every line break (`\r\n`, `\n` or `\r`) with `separator`
+pub fn flatten_newlines(value: &str, separator: &str) -> String {
+    value
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\n', separator)
+}
+
+/// DECIMAL(precision, scale) column that DKAN's datastore infers for numeric CSV columns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecimalTarget {
+    /// Total number of digits
+    pub precision: usize,
+    /// Digits after the decimal point
+    pub scale: usize,
+}
+
+impl Default for DecimalTarget {
+    fn default() -> Self {
+        DecimalTarget {
+            precision: 18,
+            scale: 6,
+        }
+    }
+}
+
+impl DecimalTarget {
+    /// True when `value` is stored without truncation: at most `scale` significant decimals
+    /// and at most `precision - scale` integer digits. Non-numeric values are not checked.
+    pub fn fits(&self, value: &str) -> bool {
+        let value = value.trim().trim_start_matches(['-', '+']);
+        if value.is_empty() || value.contains(['e', 'E']) || value.parse::<f64>().is_err() {
+            return true;
+        }
+        let (integer_part, fraction_part) = value.split_once('.').unwrap_or((value, ""));
+        let integer_digits = integer_part.trim_start_matches('0').len();
+        let fraction_digits = fraction_part.trim_end_matches('0').len();
+        fraction_digits <= self.scale && integer_digits <= self.precision.saturating_sub(self.scale)
+    }
+}
+
+/// Numeric cell of the exported CSV that DKAN would truncate or reject
+#[derive(Debug, PartialEq)]
+pub struct PrecisionWarning {
+    /// 1-based data row, not counting the header
+    pub row: usize,
+    pub column: String,
+    pub value: String,
+}
+
+/// Find the values of `columns` in the CSV that don't fit `target`
+pub fn find_precision_overflows(
+    csv_path: &str,
+    columns: &[String],
+    target: DecimalTarget,
+) -> Result<Vec<PrecisionWarning>, anyhow::Error> {
+    let mut reader = csv::Reader::from_path(csv_path)?;
+    let headers = reader.headers()?.clone();
+    let checked: Vec<(usize, &str)> = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| columns.iter().any(|column| column == header))
+        .collect();
+
+    let mut warnings = Vec::new();
+    for (row_index, record) in reader.records().enumerate() {
+        let record = record?;
+        for &(index, column) in &checked {
+            let value = record.get(index).unwrap_or_default();
+            if !target.fits(value) {
+                warnings.push(PrecisionWarning {
+                    row: row_index + 1,
+                    column: column.to_string(),
+                    value: value.to_string(),
+                });
+            }
+        }
+    }
+    return Ok(warnings);
+}
+
+/// Longest value of a column of the exported CSV, to help set `maxLength` constraints
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnMaxLength {
+    pub column: String,
+    /// Length in characters
+    pub length: usize,
+    /// 1-based data row of the first value with that length, not counting the header
+    pub row: usize,
+}
+
+/// Longest value of each of `columns` in the CSV. Columns with only empty values are left out.
+pub fn find_max_lengths(
+    csv_path: &str,
+    columns: &[String],
+) -> Result<Vec<ColumnMaxLength>, anyhow::Error> {
+    let mut reader = csv::Reader::from_path(csv_path)?;
+    let headers = reader.headers()?.clone();
+    let mut longest: Vec<(usize, ColumnMaxLength)> = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| columns.iter().any(|column| column == header))
+        .map(|(index, header)| {
+            let max_length = ColumnMaxLength {
+                column: header.to_string(),
+                length: 0,
+                row: 0,
+            };
+            (index, max_length)
+        })
+        .collect();
+
+    for (row_index, record) in reader.records().enumerate() {
+        let record = record?;
+        for (index, max_length) in longest.iter_mut() {
+            let length = record.get(*index).unwrap_or_default().chars().count();
+            if length > max_length.length {
+                max_length.length = length;
+                max_length.row = row_index + 1;
+            }
+        }
+    }
+    return Ok(longest
+        .into_iter()
+        .map(|(_, max_length)| max_length)
+        .filter(|max_length| max_length.length > 0)
+        .collect());
+}
+
+/// Exported value that no longer satisfies the JSON Schema property of its column
+#[derive(Debug, PartialEq)]
+pub struct ExportDiscrepancy {
+    /// 1-based data row, not counting the header
+    pub row: usize,
+    pub column: String,
+    pub value: String,
+    pub reason: String,
+}
+
+/// Re-read the exported CSV and check every value against the JSON Schema property of its
+/// column (type, enum, pattern and numeric bounds), to catch values the export mangled after a successful
+/// validation. `column_properties` is keyed by CSV header name; other columns are not checked.
+/// Boolean columns in `integer_booleans` (--boolean-as-integer) must hold 1/0, the others
+/// true/false.
+pub fn verify_export(
+    csv_path: &str,
+    column_properties: &HashMap<String, Value>,
+    integer_booleans: &[String],
+) -> Result<Vec<ExportDiscrepancy>, anyhow::Error> {
+    let mut reader = csv::Reader::from_path(csv_path)?;
+    let headers = reader.headers()?.clone();
+    let checked: Vec<(usize, &str, &Value)> = headers
+        .iter()
+        .enumerate()
+        .filter_map(|(index, header)| Some((index, header, column_properties.get(header)?)))
+        .collect();
+
+    let mut discrepancies = Vec::new();
+    for (row_index, record) in reader.records().enumerate() {
+        let record = record
+            .map_err(|e| anyhow::anyhow!("Exported CSV {csv_path} can't be read back: {e}"))?;
+        for &(index, column, property) in &checked {
+            let value = record.get(index).unwrap_or_default();
+            let integer_boolean = integer_booleans.iter().any(|c| c == column);
+            if let Some(reason) = check_exported_value(property, value, integer_boolean) {
+                discrepancies.push(ExportDiscrepancy {
+                    row: row_index + 1,
+                    column: column.to_string(),
+                    value: value.to_string(),
+                    reason,
+                });
+            }
+        }
+    }
+    return Ok(discrepancies);
+}
+
+/// Why `value` doesn't satisfy `property`, `None` when it does
+fn check_exported_value(property: &Value, value: &str, integer_boolean: bool) -> Option<String> {
+    let (json_type, nullable) = match property.get("type") {
+        Some(Value::String(t)) => (t.as_str(), false),
+        Some(Value::Array(types)) => (
+            types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .find(|t| *t != "null")
+                .unwrap_or("null"),
+            types.iter().any(|t| t == "null"),
+        ),
+        // Sub-schemas of allOf only carry the constraints they add
+        _ => ("", true),
+    };
+    if value.is_empty() {
+        let min_length = property
+            .get("minLength")
+            .and_then(|m| m.as_u64())
+            .unwrap_or(0);
+        let allows_empty = nullable || (json_type == "string" && min_length == 0);
+        return (!allows_empty).then(|| "required value is empty".to_string());
+    }
+
+    if json_type == "boolean" && integer_boolean {
+        return (!["1", "0"].contains(&value)).then(|| "expected 1 or 0".to_string());
+    }
+    let type_matches = match json_type {
+        "integer" => value
+            .parse::<f64>()
+            .is_ok_and(|n| n.is_finite() && n.fract() == 0.0),
+        "number" => value.parse::<f64>().is_ok_and(|n| n.is_finite()),
+        "boolean" => ["true", "false"].contains(&value.to_lowercase().as_str()),
+        _ => true,
+    };
+    if !type_matches {
+        return Some(format!("expected {json_type}"));
+    }
+    if let ("integer" | "number", Ok(number)) = (json_type, value.parse::<f64>()) {
+        if let Some(reason) = check_bounds(property, number) {
+            return Some(reason);
+        }
+    }
+
+    let matches = |allowed: &Value| match allowed.as_str() {
+        Some(allowed) => allowed.eq_ignore_ascii_case(value),
+        None => value
+            .parse::<Value>()
+            .is_ok_and(|parsed| parsed == *allowed),
+    };
+    if let Some(members) = property.get("enum").and_then(|e| e.as_array()) {
+        if !members.iter().any(matches) {
+            return Some("not one of the enum values".to_string());
+        }
+    }
+    if let Some(const_value) = property.get("const") {
+        if !matches(const_value) {
+            return Some(format!("is not the constant {const_value}"));
+        }
+    }
+
+    if let Some(pattern) = property.get("pattern").and_then(|p| p.as_str()) {
+        if let Ok(regex) = fancy_regex::Regex::new(pattern) {
+            if !regex.is_match(value).unwrap_or(true) {
+                return Some(format!("does not match pattern {pattern}"));
+            }
+        }
+    }
+
+    // e.g. the non-blank pattern of a required string next to the dictionary's own pattern
+    property
+        .get("allOf")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+        .find_map(|sub_schema| check_exported_value(sub_schema, value, integer_boolean))
+}
+
+/// Plausible window for the dates of the exported CSV (--min-date, --max-date), to catch typos
+/// like year 0202 or 2202. Bounds are inclusive `YYYY-MM-DD` dates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DateRange {
+    pub min: Option<String>,
+    pub max: Option<String>,
+}
+
+impl DateRange {
+    pub fn is_empty(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+}
+
+/// Parse a `YYYY-MM-DD` date bound
+pub fn parse_iso_date(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    match iso_date_prefix(value) {
+        Some(date) if date.len() == value.len() => Ok(date.to_string()),
+        _ => Err(format!("Expected a YYYY-MM-DD date, got '{value}'")),
+    }
+}
+
+/// `YYYY-MM-DD` start of an ISO 8601 date or date-time
+fn iso_date_prefix(value: &str) -> Option<&str> {
+    let date = value.get(..10)?;
+    let well_formed = date.bytes().enumerate().all(|(index, byte)| match index {
+        4 | 7 => byte == b'-',
+        _ => byte.is_ascii_digit(),
+    });
+    well_formed.then_some(date)
+}
+
+/// Values of the date or date-time `columns` (CSV header names) of the exported CSV that fall
+/// outside `range`. Only the date part of ISO 8601 values is compared; empty values and values
+/// in another format are skipped.
+pub fn check_date_range(
+    csv_path: &str,
+    columns: &[String],
+    range: &DateRange,
+) -> Result<Vec<ExportDiscrepancy>, anyhow::Error> {
+    let mut reader = csv::Reader::from_path(csv_path)?;
+    let headers = reader.headers()?.clone();
+    let checked: Vec<(usize, &str)> = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| columns.iter().any(|column| column == header))
+        .collect();
+
+    let mut out_of_range = Vec::new();
+    for (row_index, record) in reader.records().enumerate() {
+        let record = record
+            .map_err(|e| anyhow::anyhow!("Exported CSV {csv_path} can't be read back: {e}"))?;
+        for &(index, column) in &checked {
+            let value = record.get(index).unwrap_or_default();
+            let Some(date) = iso_date_prefix(value.trim()) else {
+                continue;
+            };
+            let reason = match (&range.min, &range.max) {
+                (Some(min), _) if date < min.as_str() => {
+                    format!("is before the minimum date {min}")
+                }
+                (_, Some(max)) if date > max.as_str() => format!("is after the maximum date {max}"),
+                _ => continue,
+            };
+            out_of_range.push(ExportDiscrepancy {
+                row: row_index + 1,
+                column: column.to_string(),
+                value: value.to_string(),
+                reason,
+            });
+        }
+    }
+    return Ok(out_of_range);
+}
+
+/// Why `number` is outside the bounds of `property`, `None` when it is within them. An exclusive
+/// bound rejects a value equal to it.
+fn check_bounds(property: &Value, number: f64) -> Option<String> {
+    let bound = |keyword: &str| property.get(keyword).and_then(|b| b.as_f64());
+    if let Some(minimum) = bound("minimum").filter(|minimum| number < *minimum) {
+        return Some(format!("is below the minimum {minimum}"));
+    }
+    if let Some(minimum) = bound("exclusiveMinimum").filter(|minimum| number <= *minimum) {
+        return Some(format!("is not above the exclusive minimum {minimum}"));
+    }
+    if let Some(maximum) = bound("maximum").filter(|maximum| number > *maximum) {
+        return Some(format!("is above the maximum {maximum}"));
+    }
+    if let Some(maximum) = bound("exclusiveMaximum").filter(|maximum| number >= *maximum) {
+        return Some(format!("is not below the exclusive maximum {maximum}"));
+    }
+    None
+}
+
+/// Checks comparing each row of the exported CSV with the previous one, e.g. for time-series
+/// instrument data. Columns are CSV header names; empty values are skipped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowOrderChecks {
+    /// Columns whose values must strictly increase from one row to the next
+    pub monotonic_increasing: Vec<String>,
+    /// Largest allowed difference between consecutive values of a column, either way
+    pub max_delta: HashMap<String, f64>,
+    /// (group column, column): values of the column must strictly increase from one row to the
+    /// next row of the same group, e.g. logger timestamps per station. Numbers are compared as
+    /// numbers, other values (ISO 8601 dates and date-times) as text.
+    pub increasing_within_group: Vec<(String, String)>,
+}
+
+impl RowOrderChecks {
+    pub fn is_empty(&self) -> bool {
+        self.monotonic_increasing.is_empty()
+            && self.max_delta.is_empty()
+            && self.increasing_within_group.is_empty()
+    }
+}
+
+/// Pair of consecutive values breaking a `RowOrderChecks` rule
+#[derive(Debug, PartialEq)]
+pub struct RowOrderViolation {
+    pub column: String,
+    /// 1-based data row of the previous value, not counting the header (0 with an empty
+    /// `previous_value` when a first value is not a number)
+    pub previous_row: usize,
+    pub previous_value: String,
+    /// 1-based data row of the offending value, not counting the header
+    pub row: usize,
+    pub value: String,
+    pub reason: String,
+}
+
+/// Parse a `group_column:column` grouped monotonic check
+pub fn parse_grouped_column(definition: &str) -> Result<(String, String), String> {
+    match definition.split_once(':') {
+        Some((group, column)) if !group.trim().is_empty() && !column.trim().is_empty() => {
+            Ok((group.trim().to_string(), column.trim().to_string()))
+        }
+        _ => Err(format!("Expected GROUP_COLUMN:COLUMN, got '{definition}'")),
+    }
+}
+
+/// Parse a `column=delta` maximum difference between consecutive values
+pub fn parse_max_delta(definition: &str) -> Result<(String, f64), String> {
+    let (column, delta) = definition
+        .split_once('=')
+        .ok_or_else(|| format!("Expected COLUMN=DELTA, got '{definition}'"))?;
+    let column = column.trim();
+    if column.is_empty() {
+        return Err(format!("Missing column name in '{definition}'"));
+    }
+    match delta.trim().parse::<f64>() {
+        Ok(delta) if delta.is_finite() && delta >= 0.0 => Ok((column.to_string(), delta)),
+        _ => Err(format!(
+            "Invalid maximum difference '{delta}' in '{definition}', expected a non-negative number"
+        )),
+    }
+}
+
+/// (1-based data row, value) of the last numeric value of a column
+type PreviousValue = (usize, String);
+
+/// Compare each non-empty value of the checked columns with the previous non-empty one
+pub fn check_row_order(
+    csv_path: &str,
+    checks: &RowOrderChecks,
+) -> Result<Vec<RowOrderViolation>, anyhow::Error> {
+    let mut reader = csv::Reader::from_path(csv_path)?;
+    let headers = reader.headers()?.clone();
+    let column_index = |column: &str| {
+        headers
+            .iter()
+            .position(|header| header == column)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Column '{column}' of the row order checks is not in the exported CSV"
+                )
+            })
+    };
+    for column in checks
+        .monotonic_increasing
+        .iter()
+        .chain(checks.max_delta.keys())
+    {
+        column_index(column)?;
+    }
+    // (group index, column index, previous value of each group) of each grouped check
+    let mut grouped: Vec<(usize, usize, HashMap<String, PreviousValue>)> = checks
+        .increasing_within_group
+        .iter()
+        .map(|(group, column)| Ok((column_index(group)?, column_index(column)?, HashMap::new())))
+        .collect::<Result<_, anyhow::Error>>()?;
+    // (index, header, previous value) of each checked column
+    let mut checked: Vec<(usize, &str, Option<PreviousValue>)> = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| {
+            checks.monotonic_increasing.iter().any(|c| c == header)
+                || checks.max_delta.contains_key(*header)
+        })
+        .map(|(index, header)| (index, header, None))
+        .collect();
+
+    let mut violations = Vec::new();
+    for (row_index, record) in reader.records().enumerate() {
+        let record = record?;
+        let row = row_index + 1;
+        for (index, column, previous) in checked.iter_mut() {
+            let value = record.get(*index).unwrap_or_default().trim();
+            if value.is_empty() {
+                continue;
+            }
+            // A value that isn't a number is reported and not compared with the next one
+            let reason = match (previous.as_ref(), value.parse::<f64>()) {
+                (_, Err(_)) => Some("is not a number".to_string()),
+                (Some((_, previous_value)), Ok(current)) => previous_value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|previous| row_order_violation(checks, column, previous, current)),
+                (None, Ok(_)) => None,
+            };
+            if let Some(reason) = reason {
+                let (previous_row, previous_value) = previous.clone().unwrap_or_default();
+                violations.push(RowOrderViolation {
+                    column: column.to_string(),
+                    previous_row,
+                    previous_value,
+                    row,
+                    value: value.to_string(),
+                    reason,
+                });
+            }
+            if value.parse::<f64>().is_ok() {
+                *previous = Some((row, value.to_string()));
+            }
+        }
+        for (group_index, index, previous_values) in grouped.iter_mut() {
+            let value = record.get(*index).unwrap_or_default().trim();
+            if value.is_empty() {
+                continue;
+            }
+            let group = record.get(*group_index).unwrap_or_default().trim();
+            if let Some((previous_row, previous_value)) = previous_values.get(group) {
+                if !increases(previous_value, value) {
+                    violations.push(RowOrderViolation {
+                        column: headers[*index].to_string(),
+                        previous_row: *previous_row,
+                        previous_value: previous_value.clone(),
+                        row,
+                        value: value.to_string(),
+                        reason: format!(
+                            "does not increase within {} '{group}'",
+                            &headers[*group_index]
+                        ),
+                    });
+                }
+            }
+            previous_values.insert(group.to_string(), (row, value.to_string()));
+        }
+    }
+    return Ok(violations);
+}
+
+/// True when `value` comes strictly after `previous`, as numbers when both are, else as text
+fn increases(previous: &str, value: &str) -> bool {
+    match (previous.parse::<f64>(), value.parse::<f64>()) {
+        (Ok(previous), Ok(value)) => value > previous,
+        _ => value > previous,
+    }
+}
+
+/// Why `value` can't follow `previous` in `column`, `None` when it can
+fn row_order_violation(
+    checks: &RowOrderChecks,
+    column: &str,
+    previous: f64,
+    current: f64,
+) -> Option<String> {
+    if checks.monotonic_increasing.iter().any(|c| c == column) && current <= previous {
+        return Some("does not increase".to_string());
+    }
+    match checks.max_delta.get(column) {
+        Some(max_delta) if (current - previous).abs() > *max_delta => {
+            Some(format!("differs by more than {max_delta}"))
+        }
+        _ => None,
+    }
+}
@@ -1,4 +1,12 @@
 #![allow(clippy::needless_return, clippy::too_many_arguments)]
 
+pub mod cache;
+pub mod export;
+pub mod mapping;
 pub mod model;
+pub mod output;
+pub mod sheet;
+pub mod summary;
+pub mod template;
+pub mod timing;
 pub mod utils;
@@ -5,15 +5,39 @@
 
 use clap::Parser;
 use dkan_importer::{
-    model::DataDictionary,
+    cache::{
+        CachedValidation, SchemaCache, ValidationCache, DEFAULT_VALIDATION_CACHE_TTL_DAYS,
+        SCHEMA_CACHE_DIR, VALIDATION_CACHE_DIR,
+    },
+    export::{
+        check_date_range, check_row_order, count_csv_rows, drop_csv_columns, find_constant_columns,
+        find_max_lengths, find_precision_overflows, parse_constant_column, parse_grouped_column,
+        parse_iso_date, parse_max_delta, verify_export, DateRange, DecimalTarget,
+        ExportDiscrepancy, ExportOptions, LineTerminator, RowCountExpectation, RowOrderChecks,
+    },
+    mapping::{unmapped_headers_warning, write_mapping_report, HeaderMapping},
+    model::{data_dictionary::is_uuid, DataDictionary, JsonSchemaDraft, Manifest, ManifestEntry},
+    output::{Output, Verbosity},
+    sheet::{SheetReference, SheetTable},
+    summary::RunSummary,
+    template::{write_template, TemplateColumn},
     utils::{
+        build_client, check_connection, check_dataset_exists, check_spreadsheet_file,
         dataset_add_distribution, delete_remote_file, generate_unique_filename,
-        upload_distribution_csv_file,
+        read_raw_sheet_headers, read_sheet_headers, resolve_distribution_filename,
+        resolve_sheet_name, upload_distribution_csv_file, write_json_schema, RetryPolicy,
+        UploadedDistribution, DEFAULT_REQUEST_TIMEOUT_SECONDS,
     },
 };
+use importer_lib::anyhow;
 use importer_lib::reqwest::blocking::Client;
+use importer_lib::serde_json::Value;
+use importer_lib::utils::normalize_string;
 use importer_lib::{ExcelValidatorBuilder, ERRORS_LOG_FILE};
 use rpassword::prompt_password;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 #[command(name = "dkan-importer")]
@@ -25,16 +49,20 @@ struct Args {
     base_url: String,
 
     /// Absolute path to the Excel file to validate (the file that will be validated against the JSON schema)
-    #[arg(short, long)]
-    excel_file: String,
+    #[arg(short, long, required_unless_present_any = ["manifest", "check_connection", "schema_only", "generate_template"])]
+    excel_file: Option<String>,
 
     /// The UUID of the DKAN data dictionary that will be used to validate the Excel file
+    #[arg(long, required_unless_present_any = ["manifest", "check_connection"])]
+    data_dictionary_id: Option<String>,
+
+    /// Optional sheet name to validate (if neither it nor --sheet-index is given, validates the first sheet)
     #[arg(long)]
-    data_dictionary_id: String,
+    sheet_name: Option<String>,
 
-    /// Optional sheet name to validate (if not specified, validates Sheet1)
-    #[arg(long, default_value = "Sheet1")]
-    sheet_name: String,
+    /// Optional 0-based index of the sheet to validate, e.g. for localized workbooks whose first sheet isn't "Sheet1"
+    #[arg(long, conflicts_with_all = ["sheet_name", "manifest"])]
+    sheet_index: Option<usize>,
 
     /// The username for the remote API authentication.
     #[arg(long)]
@@ -45,14 +73,239 @@ struct Args {
     password: Option<String>,
 
     /// The UUID of the existing DKAN dataset to add the CSV file as a distribution
+    #[arg(long, required_unless_present_any = ["manifest", "check_connection", "schema_only", "generate_template"])]
+    dataset_id: Option<String>,
+
+    /// JSON file listing several imports to run in turn (excel_file, data_dictionary_id, dataset_id, sheet_name).
+    /// Replaces --excel-file, --data-dictionary-id, --dataset-id and --sheet-name.
+    #[arg(long, conflicts_with_all = ["excel_file", "data_dictionary_id", "dataset_id"])]
+    manifest: Option<String>,
+
+    /// Name of the uploaded CSV on DKAN (e.g. "north_adriatic_samples.csv"). Defaults to the generated, timestamped name.
+    #[arg(long, conflicts_with = "manifest")]
+    distribution_filename: Option<String>,
+
+    /// Fail before upload unless the file has exactly this many data rows
+    #[arg(long, conflicts_with_all = ["min_rows", "max_rows_expected"])]
+    expect_rows: Option<usize>,
+
+    /// Fail before upload if the file has fewer data rows
+    #[arg(long)]
+    min_rows: Option<usize>,
+
+    /// Fail before upload if the file has more data rows
+    #[arg(long)]
+    max_rows_expected: Option<usize>,
+
+    /// Leave fields marked readOnly in the data dictionary out of the exported CSV
+    #[arg(long)]
+    exclude_read_only: bool,
+
+    /// Also accept a field's machine name as its column header, in addition to its title
+    #[arg(long)]
+    match_machine_names: bool,
+
+    /// Match sheet headers to column titles (and aliases) ignoring case, e.g. "temperature" for "Temperature"
+    #[arg(long)]
+    columns_case_insensitive: bool,
+
+    /// Drop optional columns that hold the same value on every row from the export (they are always reported as warnings)
+    #[arg(long)]
+    drop_constant_columns: bool,
+
+    /// Export boolean fields as 1/0 instead of true/false
+    #[arg(long)]
+    boolean_as_integer: bool,
+
+    /// Report the longest value (and its row) of each text column, to help set maxLength constraints
+    #[arg(long)]
+    report_max_lengths: bool,
+
+    /// Read the exported CSV back and check it against the schema before uploading; any mismatch aborts the upload
+    #[arg(long)]
+    verify_export: bool,
+
+    /// Earliest plausible date (YYYY-MM-DD) of date and date-time columns; an earlier date in the exported CSV aborts the upload
+    #[arg(long, value_parser = parse_iso_date)]
+    min_date: Option<String>,
+
+    /// Latest plausible date (YYYY-MM-DD) of date and date-time columns; a later date in the exported CSV aborts the upload
+    #[arg(long, value_parser = parse_iso_date)]
+    max_date: Option<String>,
+
+    /// Line terminator of the uploaded CSV: lf or crlf (by default the exported file keeps its line endings)
+    #[arg(long, value_parser = LineTerminator::from_str)]
+    line_terminator: Option<LineTerminator>,
+
+    /// Constant column appended to every exported row, as COLUMN=VALUE (repeatable, e.g. --constant source_system=LabA). Not validated.
+    #[arg(long = "constant", value_parser = parse_constant_column)]
+    constants: Vec<(String, String)>,
+
+    /// Only validate and export these columns, given as a comma-separated list of column titles (e.g. "Sample ID,Temperature")
+    #[arg(long, value_delimiter = ',', conflicts_with = "manifest")]
+    columns: Option<Vec<String>>,
+
+    /// Validate and export these columns (comma-separated titles) as text whatever their declared type, e.g. to keep leading zeros in codes
+    #[arg(long, value_delimiter = ',', conflicts_with = "manifest")]
+    force_string_columns: Vec<String>,
+
+    /// Check that a column only holds keys of a column of another sheet, as CHILD_SHEET:CHILD_COLUMN=PARENT_SHEET:PARENT_COLUMN (repeatable, e.g. --reference "Samples:Station ID=Stations:ID")
+    #[arg(long = "reference", value_parser = SheetReference::from_str, conflicts_with = "manifest")]
+    references: Vec<SheetReference>,
+
+    /// Check that these numeric columns (comma-separated titles, e.g. "Index") strictly increase from one row to the next
+    #[arg(long, value_delimiter = ',', conflicts_with = "manifest")]
+    monotonic_increasing: Vec<String>,
+
+    /// Largest allowed difference between consecutive values of a numeric column, as TITLE=DELTA (repeatable, e.g. --max-delta Depth=5)
+    #[arg(long, value_parser = parse_max_delta, conflicts_with = "manifest")]
+    max_delta: Vec<(String, f64)>,
+
+    /// Check that a column (e.g. a timestamp) strictly increases within each group of rows, as GROUP_TITLE:TITLE (repeatable, e.g. --increasing-within-group "Station:Timestamp")
+    #[arg(long, value_parser = parse_grouped_column, conflicts_with = "manifest")]
+    increasing_within_group: Vec<(String, String)>,
+
+    /// With --columns, allow leaving out required columns
+    #[arg(long, requires = "columns")]
+    allow_missing_required: bool,
+
+    /// Accept cells whose text starts with '=', which are otherwise reported as formulas left in place of their values
+    #[arg(long)]
+    allow_formulas: bool,
+
+    /// Replace line breaks inside cell values with the given separator in the exported CSV (a space if no value is given)
+    #[arg(long, num_args = 0..=1, default_missing_value = " ")]
+    flatten_newlines: Option<String>,
+
+    /// Always re-validate, ignoring results cached from a previous run on the same file and data dictionary
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Also cache passing runs, with a copy of their exported CSV in the cache directory, so an unchanged file skips validation and export
+    #[arg(long, conflicts_with = "no_cache")]
+    cache_exports: bool,
+
+    /// Number of days cached validation results are used before they expire and are deleted
+    #[arg(long, default_value_t = DEFAULT_VALIDATION_CACHE_TTL_DAYS)]
+    cache_ttl_days: u64,
+
+    /// Always download the data dictionary instead of revalidating the cached copy with its ETag
+    #[arg(long)]
+    no_schema_cache: bool,
+
+    /// Number of decimals DKAN stores for numeric columns; values with more are reported before upload
+    #[arg(long, default_value_t = DecimalTarget::default().scale)]
+    decimal_scale: usize,
+
+    /// Total number of digits DKAN stores for numeric columns
+    #[arg(long, default_value_t = DecimalTarget::default().precision)]
+    decimal_precision: usize,
+
+    /// Write a JSON summary of the run (outcome, error count, exported rows, timings) to this file
+    #[arg(long, conflicts_with = "manifest")]
+    summary_json: Option<String>,
+
+    /// Write a JSON report of how each sheet header mapped to a data dictionary field (header, normalized form, field name or null, match method) to this file
+    #[arg(long, conflicts_with = "manifest")]
+    mapping_report: Option<String>,
+
+    /// After each successful upload, print a JSON line with the dataset and distribution URLs (also with --quiet)
+    #[arg(long)]
+    json_result: bool,
+
+    /// How many times an upload or dataset update is retried after a connection error or a 5xx response (with exponential backoff from 0.5s)
+    #[arg(long, default_value_t = RetryPolicy::default().max_retries)]
+    max_retries: u32,
+
+    /// Maximum duration in seconds of each request to DKAN, including the CSV upload (raise it for very large files)
+    #[arg(long, default_value_t = DEFAULT_REQUEST_TIMEOUT_SECONDS)]
+    timeout_seconds: u64,
+
+    /// JSON Schema draft used for validation: draft-07, 2019-09 or 2020-12
+    #[arg(long, default_value = "draft-07", value_parser = JsonSchemaDraft::from_str)]
+    json_schema_draft: JsonSchemaDraft,
+
+    /// Validate and write the CSV, but don't upload it or change the dataset. The local CSV is kept for inspection.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Only check that the DKAN instance is reachable and accepts the credentials, then exit
     #[arg(long)]
-    dataset_id: String,
+    check_connection: bool,
+
+    /// Write the JSON Schema generated from the data dictionary to this file
+    #[arg(long, conflicts_with = "manifest")]
+    dump_schema: Option<String>,
+
+    /// Exit after writing the schema given by --dump-schema, without validating or uploading anything
+    #[arg(long, requires = "dump_schema")]
+    schema_only: bool,
+
+    /// Write a blank xlsx template for the data dictionary to this file (header row of field titles), then exit
+    #[arg(long, conflicts_with = "manifest")]
+    generate_template: Option<String>,
+
+    /// With --generate-template, add a second row with the field descriptions (to be removed before importing)
+    #[arg(long, requires = "generate_template")]
+    template_descriptions: bool,
+
+    /// Only print errors. The exit code still reports success or failure.
+    #[arg(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Print details about each step
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+/// Everything needed to talk to the DKAN instance, shared by all imports of a run
+struct DkanConnection {
+    base_url: String,
+    username: String,
+    password: String,
+    client: Client,
+}
+
+/// Options that apply to every import of a run
+struct ImportSettings {
+    export_options: ExportOptions,
+    /// Column titles to restrict validation and export to
+    columns: Option<Vec<String>>,
+    allow_missing_required: bool,
+    allow_formulas: bool,
+    /// Column titles validated as strings whatever their declared type
+    force_string_columns: Vec<String>,
+    references: Vec<SheetReference>,
+    /// Row order checks of the exported CSV, by column title
+    row_order: RowOrderChecks,
+    /// `None` when caching is disabled with --no-cache
+    validation_cache: Option<ValidationCache>,
+    cache_exports: bool,
+    schema_cache: Option<SchemaCache>,
+    json_schema_draft: JsonSchemaDraft,
+    dump_schema: Option<String>,
+    retry: RetryPolicy,
+    json_result: bool,
+    decimal_target: DecimalTarget,
+    summary_json: Option<String>,
+    mapping_report: Option<String>,
+    expected_rows: RowCountExpectation,
+    drop_constant_columns: bool,
+    match_machine_names: bool,
+    columns_case_insensitive: bool,
+    exclude_read_only: bool,
+    boolean_as_integer: bool,
+    verify_export: bool,
+    date_range: DateRange,
+    report_max_lengths: bool,
+    dry_run: bool,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let arguments = {
         let mut _args = Args::parse();
-        if _args.password.is_none() {
+        // A dry run never authenticates against DKAN
+        if _args.password.is_none() && !_args.dry_run {
             let _password = prompt_password("Password: ").expect("Failed to read password");
             _args.password = Some(_password);
         }
@@ -67,80 +320,1032 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         );
     }
 
-    // Get password reference for reuse
-    let password = arguments.password.unwrap();
-    let client = Client::new();
-    let data_dictionary =
-        DataDictionary::new(&arguments.base_url, &arguments.data_dictionary_id, &client)?;
-    let json_schema = data_dictionary.to_json_schema()?;
-    let title_to_name_mapping =
-        DataDictionary::create_title_to_name_mapping(&data_dictionary.fields)?;
-    let mut validator =
-        ExcelValidatorBuilder::new(&arguments.excel_file, &arguments.sheet_name, json_schema)
-            .build()?;
-    match validator.validate_excel() {
-        Ok(_) => {
-            if validator.validation_reports.is_empty() {
-                println!("✅ Validation completed!");
-            } else {
-                println!(
-                    "❌ Validation failed with {} errors",
-                    validator.validation_reports.len()
-                );
-                eprintln!("❌ Check {} for details.", ERRORS_LOG_FILE);
-                std::process::exit(1);
-            }
+    let output = Output::stdio(if arguments.quiet {
+        Verbosity::Quiet
+    } else if arguments.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    });
+
+    let connection = DkanConnection {
+        base_url: arguments.base_url,
+        username: arguments.username,
+        password: arguments.password.unwrap_or_default(),
+        client: build_client(Duration::from_secs(arguments.timeout_seconds))?,
+    };
+
+    if arguments.check_connection {
+        let status = check_connection(
+            &connection.base_url,
+            &connection.username,
+            &connection.password,
+            &connection.client,
+        );
+        if status.is_connected() {
+            output.info(status.message(&connection.base_url));
+            return Ok(());
+        }
+        output.error(status.message(&connection.base_url));
+        std::process::exit(1);
+    }
+
+    let settings = ImportSettings {
+        export_options: ExportOptions {
+            flatten_newlines: arguments.flatten_newlines,
+            constants: arguments.constants,
+            line_terminator: arguments.line_terminator,
+            ..Default::default()
+        },
+        columns: arguments.columns,
+        allow_missing_required: arguments.allow_missing_required,
+        allow_formulas: arguments.allow_formulas,
+        force_string_columns: arguments.force_string_columns,
+        references: arguments.references,
+        row_order: RowOrderChecks {
+            monotonic_increasing: arguments.monotonic_increasing,
+            max_delta: arguments.max_delta.into_iter().collect(),
+            increasing_within_group: arguments.increasing_within_group,
+        },
+        validation_cache: (!arguments.no_cache).then(|| {
+            ValidationCache::new(VALIDATION_CACHE_DIR).with_ttl(Duration::from_secs(
+                arguments.cache_ttl_days.saturating_mul(24 * 60 * 60),
+            ))
+        }),
+        cache_exports: arguments.cache_exports,
+        schema_cache: (!arguments.no_schema_cache).then(|| SchemaCache::new(SCHEMA_CACHE_DIR)),
+        json_schema_draft: arguments.json_schema_draft,
+        dump_schema: arguments.dump_schema,
+        retry: RetryPolicy {
+            max_retries: arguments.max_retries,
+            ..Default::default()
+        },
+        json_result: arguments.json_result,
+        summary_json: arguments.summary_json,
+        mapping_report: arguments.mapping_report,
+        drop_constant_columns: arguments.drop_constant_columns,
+        match_machine_names: arguments.match_machine_names,
+        columns_case_insensitive: arguments.columns_case_insensitive,
+        exclude_read_only: arguments.exclude_read_only,
+        boolean_as_integer: arguments.boolean_as_integer,
+        verify_export: arguments.verify_export,
+        date_range: DateRange {
+            min: arguments.min_date,
+            max: arguments.max_date,
+        },
+        report_max_lengths: arguments.report_max_lengths,
+        dry_run: arguments.dry_run,
+        expected_rows: RowCountExpectation {
+            exact: arguments.expect_rows,
+            min: arguments.min_rows,
+            max: arguments.max_rows_expected,
+        },
+        decimal_target: DecimalTarget {
+            precision: arguments.decimal_precision,
+            scale: arguments.decimal_scale,
+        },
+    };
+
+    if arguments.schema_only {
+        // clap guarantees the data dictionary id when --manifest is not given, and --dump-schema with --schema-only
+        let data_dictionary = fetch_data_dictionary(
+            &connection,
+            &settings,
+            &arguments.data_dictionary_id.unwrap(),
+        )?;
+        let json_schema = data_dictionary.to_json_schema_with_draft(settings.json_schema_draft)?;
+        let schema_path = settings.dump_schema.unwrap();
+        write_json_schema(&json_schema, &schema_path)?;
+        output.info(format!("✅ JSON Schema written to {schema_path}"));
+        return Ok(());
+    }
+
+    if let Some(template_path) = &arguments.generate_template {
+        // clap guarantees the data dictionary id when --manifest is not given
+        let data_dictionary = fetch_data_dictionary(
+            &connection,
+            &settings,
+            arguments.data_dictionary_id.as_deref().unwrap(),
+        )?;
+        let columns = TemplateColumn::from_fields(&data_dictionary.fields)?;
+        write_template(template_path, &columns, arguments.template_descriptions)?;
+        output.info(format!(
+            "✅ Template with {} columns written to {template_path}",
+            columns.len()
+        ));
+        return Ok(());
+    }
+
+    if let Some(manifest_path) = arguments.manifest {
+        let manifest = Manifest::from_file(&manifest_path)?;
+        let summary = manifest.run(|entry| {
+            output.info(format!("▶️ Importing {}", entry.label()));
+            run_import(&connection, &settings, &output, entry)
+        });
+        if summary.has_failures() {
+            output.error(summary.report());
+            std::process::exit(1);
         }
+        output.info(summary.report());
+        return Ok(());
+    }
+
+    // clap guarantees these are present when --manifest is not given
+    let excel_file = arguments.excel_file.unwrap();
+    let sheet_name = match resolve_sheet_name(
+        &excel_file,
+        arguments.sheet_name.as_deref(),
+        arguments.sheet_index,
+    ) {
+        Ok(sheet_name) => sheet_name,
         Err(e) => {
-            eprintln!("❌ Validation failed with error: {e}");
-            eprintln!("❌ Check {} for details.", ERRORS_LOG_FILE);
+            output.error(format!("❌ {e}"));
             std::process::exit(1);
         }
+    };
+    let entry = ManifestEntry {
+        excel_file,
+        data_dictionary_id: arguments.data_dictionary_id.unwrap(),
+        dataset_id: arguments.dataset_id.unwrap(),
+        sheet_name: Some(sheet_name),
+        distribution_filename: arguments.distribution_filename,
+    };
+    if let Err(e) = run_import(&connection, &settings, &output, &entry) {
+        output.error(format!("❌ {e}"));
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Fetch the data dictionary through the schema cache
+fn fetch_data_dictionary(
+    connection: &DkanConnection,
+    settings: &ImportSettings,
+    data_dictionary_id: &str,
+) -> Result<DataDictionary, anyhow::Error> {
+    DataDictionary::new_with_cache(
+        &connection.base_url,
+        data_dictionary_id,
+        &connection.client,
+        settings.schema_cache.as_ref(),
+    )
+}
+
+/// Report the first items of a list, one indented line each, then how many were left out, so a
+/// sheet with thousands of problems doesn't flood the terminal
+fn report_listed<T>(report: impl Fn(String), items: &[T], describe: impl Fn(&T) -> String) {
+    const MAX_LISTED: usize = 10;
+
+    for item in items.iter().take(MAX_LISTED) {
+        report(format!("   {}", describe(item)));
+    }
+    if items.len() > MAX_LISTED {
+        report(format!("   ... and {} more", items.len() - MAX_LISTED));
+    }
+}
+
+/// Line describing an exported value that failed a check of the exported CSV
+fn describe_discrepancy(discrepancy: &ExportDiscrepancy) -> String {
+    format!(
+        "row {}, column '{}': '{}' {}",
+        discrepancy.row, discrepancy.column, discrepancy.value, discrepancy.reason
+    )
+}
+
+/// Warn about numeric values DKAN would truncate when storing them as DECIMAL(precision, scale)
+fn warn_precision_overflows(
+    output: &Output,
+    csv_path: &str,
+    number_columns: &[String],
+    target: DecimalTarget,
+) -> Result<(), anyhow::Error> {
+    let warnings = find_precision_overflows(csv_path, number_columns, target)?;
+    if warnings.is_empty() {
+        return Ok(());
     }
+    output.warn(format!(
+        "⚠️ {} numeric values exceed DECIMAL({},{}) and may be truncated by DKAN:",
+        warnings.len(),
+        target.precision,
+        target.scale
+    ));
+    report_listed(
+        |line| output.warn(line),
+        &warnings,
+        |warning| {
+            format!(
+                "row {}, column '{}': {}",
+                warning.row, warning.column, warning.value
+            )
+        },
+    );
+    Ok(())
+}
+
+/// Check the exported CSV against the schema properties of its columns, so values mangled by the
+/// export are caught before they are uploaded
+fn verify_exported_csv(
+    output: &Output,
+    csv_path: &str,
+    column_properties: &HashMap<String, Value>,
+    integer_booleans: &[String],
+) -> Result<(), anyhow::Error> {
+    let discrepancies = verify_export(csv_path, column_properties, integer_booleans)?;
+    if discrepancies.is_empty() {
+        output.detail("🔍 Exported CSV verified against the schema");
+        return Ok(());
+    }
+    output.error(format!(
+        "❌ {} exported values don't match the schema:",
+        discrepancies.len()
+    ));
+    report_listed(
+        |line| output.error(line),
+        &discrepancies,
+        describe_discrepancy,
+    );
+    Err(anyhow::anyhow!(
+        "Export verification of {csv_path} found {} values not matching the schema, nothing was uploaded",
+        discrepancies.len()
+    ))
+}
 
-    let csv_filename = generate_unique_filename(&arguments.dataset_id, &arguments.sheet_name);
-    // Create a csv since the validation is successful. Use schema-aware parsing for proper date formatting.
-    match validator.export_to_csv(&csv_filename, title_to_name_mapping) {
-        Ok(_) => {
-            println!("✅ CSV file created: {csv_filename}");
+/// Report required cells left blank, row by row, before validation. A blank cell may otherwise
+/// only show up as a type or pattern error, or pass as null.
+fn check_blank_required_cells(
+    output: &Output,
+    excel_file: &str,
+    sheet_name: &str,
+    sheet_table: &SheetTable,
+    required_headers: &[String],
+) -> Result<(), anyhow::Error> {
+    let blank_cells = sheet_table.blank_cells(required_headers);
+    if blank_cells.is_empty() {
+        return Ok(());
+    }
+    output.error(format!(
+        "❌ {} required cells are blank in sheet '{sheet_name}':",
+        blank_cells.len()
+    ));
+    report_listed(
+        |line| output.error(line),
+        &blank_cells,
+        |blank_cell| {
+            format!(
+                "row {}, column '{}': required field is blank",
+                blank_cell.row, blank_cell.column
+            )
+        },
+    );
+    Err(anyhow::anyhow!(
+        "Sheet '{sheet_name}' of {excel_file} has {} blank required cells",
+        blank_cells.len()
+    ))
+}
+
+/// Report cells holding a formula instead of its value, row by row, before validation, where
+/// they would be converted like any other text
+fn check_formula_cells(
+    output: &Output,
+    excel_file: &str,
+    sheet_name: &str,
+    sheet_table: &SheetTable,
+    columns: &[String],
+) -> Result<(), anyhow::Error> {
+    let formula_cells = sheet_table.formula_cells(columns);
+    if formula_cells.is_empty() {
+        return Ok(());
+    }
+    output.error(format!(
+        "❌ {} cells contain a formula in sheet '{sheet_name}':",
+        formula_cells.len()
+    ));
+    report_listed(
+        |line| output.error(line),
+        &formula_cells,
+        |formula_cell| {
+            format!(
+                "row {}, column '{}': cell contains a formula, not a value ('{}')",
+                formula_cell.row, formula_cell.column, formula_cell.formula
+            )
+        },
+    );
+    Err(anyhow::anyhow!(
+        "Sheet '{sheet_name}' of {excel_file} has {} formula cells, save it with the computed values or pass --allow-formulas",
+        formula_cells.len()
+    ))
+}
+
+/// Report values of uuid columns that don't have the 8-4-4-4-12 hex shape, row by row, before
+/// validation, which would only report them as a pattern mismatch
+fn check_malformed_uuids(
+    output: &Output,
+    excel_file: &str,
+    sheet_name: &str,
+    sheet_table: &SheetTable,
+    uuid_headers: &[String],
+) -> Result<(), anyhow::Error> {
+    let mut malformed = Vec::new();
+    for column in uuid_headers {
+        for (row, value) in sheet_table.column_values(column)? {
+            if !is_uuid(value) {
+                malformed.push((row, column, value));
+            }
         }
-        Err(e) => {
-            panic!("❌ Failed to create CSV with error: {e}");
+    }
+    if malformed.is_empty() {
+        return Ok(());
+    }
+    malformed.sort_by_key(|(row, _, _)| *row);
+    output.error(format!(
+        "❌ {} malformed UUIDs in sheet '{sheet_name}':",
+        malformed.len()
+    ));
+    report_listed(
+        |line| output.error(line),
+        &malformed,
+        |(row, column, value)| {
+            format!("row {row}, column '{column}': malformed UUID '{value}', expected 8-4-4-4-12 hexadecimal digits")
+        },
+    );
+    Err(anyhow::anyhow!(
+        "Sheet '{sheet_name}' of {excel_file} has {} malformed UUIDs",
+        malformed.len()
+    ))
+}
+
+/// Check the cross-sheet references given with --reference, reporting child values with no
+/// matching parent key
+fn check_sheet_references(
+    output: &Output,
+    excel_file: &str,
+    references: &[SheetReference],
+) -> Result<(), anyhow::Error> {
+    let mut broken_count = 0;
+    for reference in references {
+        let broken = reference.check(excel_file)?;
+        if broken.is_empty() {
+            output.detail(format!("🔑 Reference {reference} checked"));
+            continue;
         }
+        output.error(format!(
+            "❌ {} values of '{}' in sheet '{}' are not a '{}' of sheet '{}':",
+            broken.len(),
+            reference.child_column,
+            reference.child_sheet,
+            reference.parent_column,
+            reference.parent_sheet
+        ));
+        report_listed(
+            |line| output.error(line),
+            &broken,
+            |broken_reference| {
+                format!("row {}: '{}'", broken_reference.row, broken_reference.value)
+            },
+        );
+        broken_count += broken.len();
+    }
+    if broken_count > 0 {
+        return Err(anyhow::anyhow!(
+            "Cross-sheet reference check of {excel_file} found {broken_count} values with no matching key"
+        ));
     }
+    Ok(())
+}
 
-    let file_url = upload_distribution_csv_file(
-        &arguments.base_url,
+/// Check the dates of the exported CSV against --min-date and --max-date
+fn check_exported_dates(
+    output: &Output,
+    csv_path: &str,
+    date_columns: &[String],
+    date_range: &DateRange,
+) -> Result<(), anyhow::Error> {
+    let out_of_range = check_date_range(csv_path, date_columns, date_range)?;
+    if out_of_range.is_empty() {
+        output.detail("🔍 Dates are within the expected range");
+        return Ok(());
+    }
+    output.error(format!(
+        "❌ {} dates are out of the expected range:",
+        out_of_range.len()
+    ));
+    report_listed(
+        |line| output.error(line),
+        &out_of_range,
+        describe_discrepancy,
+    );
+    Err(anyhow::anyhow!(
+        "Date range check of {csv_path} found {} out-of-range dates, nothing was uploaded",
+        out_of_range.len()
+    ))
+}
+
+/// Check consecutive rows of the exported CSV against --monotonic-increasing, --max-delta and
+/// --increasing-within-group
+fn check_exported_row_order(
+    output: &Output,
+    csv_path: &str,
+    checks: &RowOrderChecks,
+) -> Result<(), anyhow::Error> {
+    let violations = check_row_order(csv_path, checks)?;
+    if violations.is_empty() {
+        output.detail("🔍 Row order checks passed");
+        return Ok(());
+    }
+    output.error(format!(
+        "❌ {} values break the row order checks:",
+        violations.len()
+    ));
+    report_listed(
+        |line| output.error(line),
+        &violations,
+        |violation| {
+            format!(
+                "rows {} → {}, column '{}': '{}' → '{}' {}",
+                violation.previous_row,
+                violation.row,
+                violation.column,
+                violation.previous_value,
+                violation.value,
+                violation.reason
+            )
+        },
+    );
+    Err(anyhow::anyhow!(
+        "Row order checks of {csv_path} found {} violations, nothing was uploaded",
+        violations.len()
+    ))
+}
+
+/// Warn about columns holding the same value on every row and, when asked to, drop the optional
+/// ones from the CSV. Columns added with --constant are constant on purpose and left alone.
+fn handle_constant_columns(
+    output: &Output,
+    csv_path: &str,
+    export_options: &ExportOptions,
+    required_columns: &[String],
+    drop: bool,
+) -> Result<(), anyhow::Error> {
+    let constant_columns: Vec<(String, String)> = find_constant_columns(csv_path)?
+        .into_iter()
+        .filter(|(column, _)| !export_options.constants.iter().any(|(c, _)| c == column))
+        .collect();
+    let mut dropped = Vec::new();
+    for (column, value) in &constant_columns {
+        let droppable = drop && !required_columns.contains(column);
+        output.warn(format!(
+            "⚠️ Column '{column}' has the same value '{value}' on every row{}",
+            if droppable {
+                ", dropped from the export"
+            } else {
+                ""
+            }
+        ));
+        if droppable {
+            dropped.push(column.clone());
+        }
+    }
+    if dropped.is_empty() {
+        return Ok(());
+    }
+    drop_csv_columns(csv_path, &dropped, export_options.line_terminator)
+}
+
+/// Validate one Excel sheet against its data dictionary, then upload it as a CSV distribution of
+/// the dataset. The outcome is written to the --summary-json file, whether the import succeeded or not.
+fn run_import(
+    connection: &DkanConnection,
+    settings: &ImportSettings,
+    output: &Output,
+    entry: &ManifestEntry,
+) -> Result<(), anyhow::Error> {
+    let mut summary = RunSummary::new(entry);
+    let result = import_entry(connection, settings, output, entry, &mut summary);
+    if let Some(summary_path) = &settings.summary_json {
+        summary.error = result.as_ref().err().map(|e| e.to_string());
+        summary.write(summary_path)?;
+        output.detail(format!("📋 Run summary written to {summary_path}"));
+    }
+    result
+}
+
+fn import_entry(
+    connection: &DkanConnection,
+    settings: &ImportSettings,
+    output: &Output,
+    entry: &ManifestEntry,
+    summary: &mut RunSummary,
+) -> Result<(), anyhow::Error> {
+    check_spreadsheet_file(&entry.excel_file)?;
+    // Manifest entries without a sheet name use the first sheet, like the command line
+    let sheet_name = resolve_sheet_name(&entry.excel_file, entry.sheet_name.as_deref(), None)?;
+    summary.sheet_name = sheet_name.clone();
+    // Fail before any parsing if the target dataset doesn't exist. A dry run doesn't touch
+    // the dataset, so it needs no credentials.
+    if !settings.dry_run {
+        check_dataset_exists(
+            &connection.base_url,
+            &entry.dataset_id,
+            &connection.username,
+            &connection.password,
+            &connection.client,
+        )?;
+    }
+    let data_dictionary = fetch_data_dictionary(connection, settings, &entry.data_dictionary_id)?;
+    output.detail(format!(
+        "📖 Data dictionary \"{}\" fetched from {}",
+        data_dictionary.name, data_dictionary.url
+    ));
+    let mut json_schema = data_dictionary.to_json_schema_with_draft(settings.json_schema_draft)?;
+    if let Some(schema_path) = &settings.dump_schema {
+        write_json_schema(&json_schema, schema_path)?;
+        output.info(format!("✅ JSON Schema written to {schema_path}"));
+    }
+    let mut title_to_name_mapping =
+        DataDictionary::create_title_to_name_mapping(&data_dictionary.fields)?;
+    output.detail(format!(
+        "🔗 {} column titles mapped to data dictionary fields",
+        title_to_name_mapping.len()
+    ));
+
+    let mut export_options = settings.export_options.clone();
+    for (index, (column, _)) in export_options.constants.iter().enumerate() {
+        if title_to_name_mapping.values().any(|name| name == column)
+            || export_options.constants[..index]
+                .iter()
+                .any(|(other, _)| other == column)
+        {
+            return Err(anyhow::anyhow!(
+                "Constant column '{column}' collides with a data dictionary field or another constant column"
+            ));
+        }
+    }
+    if let Some(columns) = &settings.columns {
+        DataDictionary::restrict_schema_to_columns(
+            &mut json_schema,
+            columns,
+            settings.allow_missing_required,
+        )?;
+        // The exported CSV uses field names as headers
+        export_options.columns = Some(
+            columns
+                .iter()
+                .filter_map(|column| title_to_name_mapping.get(&normalize_string(column)))
+                .cloned()
+                .collect(),
+        );
+    }
+
+    DataDictionary::force_string_properties(&mut json_schema, &settings.force_string_columns)?;
+
+    // The exported CSV uses field names as headers
+    let field_names_for = |titles: &[String]| -> Vec<String> {
+        titles
+            .iter()
+            .filter_map(|title| title_to_name_mapping.get(title))
+            .cloned()
+            .collect()
+    };
+    let number_columns = field_names_for(&DataDictionary::number_properties(&json_schema));
+    let required_columns: Vec<String> = json_schema
+        .get("required")
+        .and_then(|required| required.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|title| title.as_str().and_then(|t| title_to_name_mapping.get(t)))
+        .cloned()
+        .collect();
+    export_options.canonical_enums = DataDictionary::enum_properties(&json_schema)
+        .into_iter()
+        .filter_map(|(title, members)| Some((title_to_name_mapping.get(&title)?.clone(), members)))
+        .collect();
+    export_options.uuid_columns = field_names_for(&DataDictionary::uuid_properties(&json_schema));
+    if settings.boolean_as_integer {
+        export_options.integer_booleans =
+            field_names_for(&DataDictionary::boolean_properties(&json_schema));
+    }
+    let read_only_columns = field_names_for(&DataDictionary::read_only_properties(&json_schema));
+    let date_columns = field_names_for(&DataDictionary::date_properties(&json_schema));
+    let string_columns = field_names_for(&DataDictionary::string_properties(&json_schema));
+    let column_properties: HashMap<String, Value> =
+        match json_schema.get("properties").and_then(|p| p.as_object()) {
+            Some(properties) if settings.verify_export => properties
+                .iter()
+                .filter_map(|(title, property)| {
+                    Some((title_to_name_mapping.get(title)?.clone(), property.clone()))
+                })
+                .collect(),
+            _ => HashMap::new(),
+        };
+    let field_name = |title: &String| {
+        title_to_name_mapping
+            .get(&normalize_string(title))
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Column '{title}' of the row order checks is not a data dictionary field"
+                )
+            })
+    };
+    let row_order = RowOrderChecks {
+        monotonic_increasing: settings
+            .row_order
+            .monotonic_increasing
+            .iter()
+            .map(field_name)
+            .collect::<Result<_, _>>()?,
+        max_delta: settings
+            .row_order
+            .max_delta
+            .iter()
+            .map(|(title, delta)| Ok((field_name(title)?, *delta)))
+            .collect::<Result<_, anyhow::Error>>()?,
+        increasing_within_group: settings
+            .row_order
+            .increasing_within_group
+            .iter()
+            .map(|(group, title)| Ok((field_name(group)?, field_name(title)?)))
+            .collect::<Result<_, anyhow::Error>>()?,
+    };
+    if settings.match_machine_names {
+        DataDictionary::add_machine_name_aliases(&mut json_schema, &mut title_to_name_mapping)?;
+    }
+    DataDictionary::add_field_aliases(
+        &mut json_schema,
+        &data_dictionary.fields,
+        &mut title_to_name_mapping,
+    )?;
+    let headers = read_sheet_headers(&entry.excel_file, &sheet_name)?;
+    if settings.columns_case_insensitive {
+        DataDictionary::add_case_insensitive_aliases(
+            &mut json_schema,
+            &mut title_to_name_mapping,
+            &headers,
+        )?;
+    }
+
+    DataDictionary::check_mapping_consistency(
+        &json_schema,
+        &data_dictionary.fields,
+        &title_to_name_mapping,
+    )?;
+
+    let header_mappings = HeaderMapping::from_headers(
+        &read_raw_sheet_headers(&entry.excel_file, &sheet_name)?,
+        &data_dictionary.fields,
+        &title_to_name_mapping,
+    )?;
+    if let Some(warning) = unmapped_headers_warning(&header_mappings) {
+        output.warn(warning);
+    }
+    if let Some(report_path) = &settings.mapping_report {
+        write_mapping_report(&header_mappings, report_path)?;
+        output.detail(format!("🗺️ Header mapping report written to {report_path}"));
+    }
+
+    // A required column missing from the sheet is reported once, before any row is validated
+    let missing_columns = DataDictionary::missing_required_columns(&json_schema, &headers);
+    if !missing_columns.is_empty() {
+        output.error(format!(
+            "❌ Required columns missing from sheet '{}': {}",
+            sheet_name,
+            missing_columns.join(", ")
+        ));
+        return Err(anyhow::anyhow!(
+            "Sheet '{}' of {} is missing {} required columns",
+            sheet_name,
+            entry.excel_file,
+            missing_columns.len()
+        ));
+    }
+
+    // Only the columns checked before validation are read, and only when there are any. Formulas
+    // are looked for in every validated column.
+    let required_headers = DataDictionary::required_headers(&json_schema, &headers);
+    let uuid_headers: Vec<String> = DataDictionary::uuid_properties(&json_schema)
+        .into_iter()
+        .filter(|title| headers.contains(title))
+        .collect();
+    let formula_headers: Vec<String> = if settings.allow_formulas {
+        Vec::new()
+    } else {
+        headers
+            .iter()
+            .filter(|header| json_schema["properties"].get(header.as_str()).is_some())
+            .cloned()
+            .collect()
+    };
+    let mut checked_headers: Vec<String> = Vec::new();
+    for header in required_headers
+        .iter()
+        .chain(&uuid_headers)
+        .chain(&formula_headers)
+    {
+        if !checked_headers.contains(header) {
+            checked_headers.push(header.clone());
+        }
+    }
+    if !checked_headers.is_empty() {
+        let sheet_table = SheetTable::read(&entry.excel_file, &sheet_name, &checked_headers)?;
+        summary.total_rows = Some(sheet_table.rows.len());
+        check_blank_required_cells(
+            output,
+            &entry.excel_file,
+            &sheet_name,
+            &sheet_table,
+            &required_headers,
+        )?;
+        check_malformed_uuids(
+            output,
+            &entry.excel_file,
+            &sheet_name,
+            &sheet_table,
+            &uuid_headers,
+        )?;
+        check_formula_cells(
+            output,
+            &entry.excel_file,
+            &sheet_name,
+            &sheet_table,
+            &formula_headers,
+        )?;
+    }
+    check_sheet_references(output, &entry.excel_file, &settings.references)?;
+
+    // A validation of the exact same schema, mapping, file and sheet is reported from the cache
+    // without parsing the workbook again
+    let cache_entry = match &settings.validation_cache {
+        Some(cache) => {
+            let excel_content = std::fs::read(&entry.excel_file).map_err(|e| {
+                anyhow::anyhow!("Failed to read Excel file {}: {e}", entry.excel_file)
+            })?;
+            let key = ValidationCache::key(
+                &json_schema,
+                &title_to_name_mapping,
+                &excel_content,
+                &sheet_name,
+            );
+            Some((cache, key))
+        }
+        None => None,
+    };
+    let cached = cache_entry.as_ref().and_then(|(cache, key)| cache.get(key));
+    if let Some(cached) = cached.as_ref().filter(|cached| !cached.passed()) {
+        summary.error_count = cached.error_count;
+        summary.invalid_rows = Some(cached.error_count);
+        output.error(format!(
+            "❌ Validation failed with {} errors (cached result from {}, use --no-cache to re-validate and write {})",
+            cached.error_count, cached.created_at, ERRORS_LOG_FILE
+        ));
+        return Err(anyhow::anyhow!(
+            "Validation of {} failed with {} errors (cached)",
+            entry.excel_file,
+            cached.error_count
+        ));
+    }
+
+    let csv_filename = generate_unique_filename(&entry.dataset_id, &sheet_name);
+    let export_start = match (cached, &cache_entry) {
+        // Only cached with --cache-exports, together with the export
+        (Some(cached), Some((cache, key))) => {
+            output.info(format!(
+                "✅ Validation completed! (cached result from {}, use --no-cache to re-validate)",
+                cached.created_at
+            ));
+            summary.invalid_rows = Some(0);
+            let export_start = Instant::now();
+            cache.restore_export(key, &csv_filename)?;
+            export_start
+        }
+        _ => {
+            let validation_start = Instant::now();
+            let mut validator =
+                ExcelValidatorBuilder::new(&entry.excel_file, &sheet_name, json_schema).build()?;
+            let validation_result = validator.validate_excel();
+            summary.timings.validation = Some(validation_start.elapsed());
+            match validation_result {
+                Ok(_) => {
+                    if validator.validation_reports.is_empty() {
+                        output.info("✅ Validation completed!");
+                        summary.invalid_rows = Some(0);
+                    } else {
+                        summary.error_count = validator.validation_reports.len();
+                        // One report per row with errors
+                        summary.invalid_rows = Some(validator.validation_reports.len());
+                        output
+                            .validation_failed(validator.validation_reports.len(), ERRORS_LOG_FILE);
+                        if let Some((cache, key)) = &cache_entry {
+                            let cached = CachedValidation::new(validator.validation_reports.len());
+                            if let Err(e) = cache.store(key, &cached) {
+                                output
+                                    .warn(format!("⚠️ Failed to cache the validation result: {e}"));
+                            }
+                        }
+                        return Err(anyhow::anyhow!(
+                            "Validation of {} failed with {} errors",
+                            entry.excel_file,
+                            validator.validation_reports.len()
+                        ));
+                    }
+                }
+                Err(e) => {
+                    output.error(format!("❌ Validation failed with error: {e}"));
+                    output.error(format!("❌ Check {} for details.", ERRORS_LOG_FILE));
+                    return Err(anyhow::anyhow!(
+                        "Validation of {} failed with error: {e}",
+                        entry.excel_file
+                    ));
+                }
+            }
+
+            // Create a csv since the validation is successful. Use schema-aware parsing for proper date formatting.
+            let export_start = Instant::now();
+            validator
+                .export_to_csv(&csv_filename, title_to_name_mapping)
+                .map_err(|e| anyhow::anyhow!("Failed to create CSV with error: {e}"))?;
+            if let (true, Some((cache, key))) = (settings.cache_exports, &cache_entry) {
+                if let Err(e) = cache.store_passed(key, &csv_filename) {
+                    output.warn(format!("⚠️ Failed to cache the validation result: {e}"));
+                }
+            }
+            export_start
+        }
+    };
+    // From here on a failed check or upload doesn't leave the CSV behind
+    let _exported_csv = ExportedCsv {
+        path: &csv_filename,
+        keep: settings.dry_run,
+    };
+    export_options.apply(&csv_filename)?;
+    if settings.exclude_read_only && !read_only_columns.is_empty() {
+        drop_csv_columns(
+            &csv_filename,
+            &read_only_columns,
+            export_options.line_terminator,
+        )?;
+        output.detail(format!(
+            "🧹 Read-only columns left out of the export: {}",
+            read_only_columns.join(", ")
+        ));
+    }
+    handle_constant_columns(
+        output,
         &csv_filename,
-        &arguments.username,
-        &password,
-        &client,
+        &export_options,
+        &required_columns,
+        settings.drop_constant_columns,
+    )?;
+    output.info(format!("✅ CSV file created: {csv_filename}"));
+    summary.timings.export = Some(export_start.elapsed());
+    if settings.verify_export {
+        verify_exported_csv(
+            output,
+            &csv_filename,
+            &column_properties,
+            &export_options.integer_booleans,
+        )?;
+    }
+    if !settings.date_range.is_empty() {
+        check_exported_dates(output, &csv_filename, &date_columns, &settings.date_range)?;
+    }
+    if !row_order.is_empty() {
+        check_exported_row_order(output, &csv_filename, &row_order)?;
+    }
+    warn_precision_overflows(
+        output,
+        &csv_filename,
+        &number_columns,
+        settings.decimal_target,
+    )?;
+    summary.timings.row_count = count_csv_rows(&csv_filename)?;
+    // Every row of a validated sheet is exported, when no check read the sheet before
+    summary.total_rows.get_or_insert(summary.timings.row_count);
+    if settings.report_max_lengths {
+        summary.max_lengths = Some(find_max_lengths(&csv_filename, &string_columns)?);
+    }
+    settings
+        .expected_rows
+        .check(summary.timings.row_count)
+        .map_err(|e| anyhow::anyhow!("{e} ({}), nothing was uploaded", entry.excel_file))?;
+    summary.timings.uploaded_bytes = std::fs::metadata(&csv_filename)?.len();
+    if settings.dry_run {
+        output.info(format!(
+            "🧪 Dry run: CSV written to {csv_filename}, no upload performed"
+        ));
+    } else {
+        upload_csv(
+            connection,
+            settings,
+            output,
+            entry,
+            &data_dictionary.url,
+            &csv_filename,
+            summary,
+        )?;
+    }
+
+    output.info(format!("⏱️ {}", summary.timings.summary()));
+    if let Some(max_lengths) = &summary.max_lengths {
+        output.info("📏 Longest values per text column:");
+        for max_length in max_lengths {
+            output.info(format!(
+                "   {}: {} characters (row {})",
+                max_length.column, max_length.length, max_length.row
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Exported CSV removed from the local filesystem when an import returns, unless kept for a
+/// --dry-run. A successful upload has already removed it.
+struct ExportedCsv<'a> {
+    path: &'a str,
+    keep: bool,
+}
+
+impl Drop for ExportedCsv<'_> {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = std::fs::remove_file(self.path);
+        }
+    }
+}
+
+/// Upload the exported CSV, make it the dataset's distribution for this data dictionary (deleting
+/// the file it replaces) and remove the local copy
+fn upload_csv(
+    connection: &DkanConnection,
+    settings: &ImportSettings,
+    output: &Output,
+    entry: &ManifestEntry,
+    data_dictionary_url: &str,
+    csv_filename: &str,
+    summary: &mut RunSummary,
+) -> Result<(), anyhow::Error> {
+    let distribution_filename =
+        resolve_distribution_filename(entry.distribution_filename.as_deref(), csv_filename)?;
+    let upload_start = Instant::now();
+    let file_url = upload_distribution_csv_file(
+        &connection.base_url,
+        csv_filename,
+        &distribution_filename,
+        &connection.username,
+        &connection.password,
+        &connection.client,
+        &settings.retry,
+        output,
     )?;
 
     let optional_previous_csv_filename = dataset_add_distribution(
-        &arguments.base_url,
-        &arguments.dataset_id,
-        &csv_filename,
+        &connection.base_url,
+        &entry.dataset_id,
+        &distribution_filename,
         &file_url,
-        &data_dictionary.url,
-        &arguments.username,
-        &password,
-        &client,
+        data_dictionary_url,
+        &connection.username,
+        &connection.password,
+        &connection.client,
+        &settings.retry,
+        output,
     )?;
+    summary.timings.upload = Some(upload_start.elapsed());
 
-    // Clean up previous CSV file if one was replaced
-    if let Some(previous_csv_filename) = optional_previous_csv_filename {
+    // Clean up previous CSV file if one was replaced. With a fixed --distribution-filename the
+    // previous distribution can carry the same name as the new one, which must not be deleted.
+    if let Some(previous_csv_filename) = optional_previous_csv_filename
+        .filter(|previous_csv_filename| previous_csv_filename != &distribution_filename)
+    {
         delete_remote_file(
-            &arguments.base_url,
+            &connection.base_url,
             &previous_csv_filename,
-            &arguments.username,
-            &password,
-            &client,
+            &connection.username,
+            &connection.password,
+            &connection.client,
+            &settings.retry,
+            output,
         )?;
     }
 
     // Also delete the CSV file from the local filesystem
-    std::fs::remove_file(&csv_filename)?;
+    std::fs::remove_file(csv_filename)?;
+
+    let uploaded = UploadedDistribution::new(
+        &connection.base_url,
+        &entry.dataset_id,
+        &distribution_filename,
+        &file_url,
+    );
+    output.info(format!(
+        "🔗 Distribution URL: {}",
+        uploaded.distribution_url
+    ));
+    output.info(format!("🔗 Dataset URL: {}", uploaded.dataset_url));
+    if settings.json_result {
+        output.result(uploaded.to_json());
+    }
 
     Ok(())
 }
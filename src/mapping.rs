@@ -0,0 +1,104 @@
+use crate::model::DataDictionary;
+use importer_lib::anyhow;
+use importer_lib::serde_json::{self, Value};
+use importer_lib::utils::normalize_string;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// How a sheet header was matched to a data dictionary field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMethod {
+    /// The header is the field's title (or name, for untitled fields) as written
+    Exact,
+    /// The header only matches the field's title once normalized (e.g. extra whitespace)
+    Normalized,
+    /// The header is one of the field's `aliases`, or its machine name with --match-machine-names
+    Alias,
+    /// The header matches a title only when case is ignored, with --columns-case-insensitive
+    CaseInsensitive,
+}
+
+/// One row of the --mapping-report file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HeaderMapping {
+    /// Header as written in the sheet
+    pub header: String,
+    pub normalized: String,
+    /// Machine name of the matched field, `None` when the header matches no field
+    pub field: Option<String>,
+    pub method: Option<MatchMethod>,
+}
+
+impl HeaderMapping {
+    /// Map each non-blank sheet header to its field. `title_to_name_mapping` is the mapping used
+    /// for the export, including the aliases added to it; headers matching a field title in
+    /// `dkan_fields` are exact matches, the others matched through that mapping are aliases
+    /// (case-insensitive ones when they equal a title ignoring case).
+    pub fn from_headers(
+        headers: &[String],
+        dkan_fields: &Value,
+        title_to_name_mapping: &HashMap<String, String>,
+    ) -> Result<Vec<HeaderMapping>, anyhow::Error> {
+        let titles = DataDictionary::create_title_to_name_mapping(dkan_fields)?;
+        let mappings = headers
+            .iter()
+            .filter(|header| !header.trim().is_empty())
+            .map(|header| {
+                let normalized = normalize_string(header);
+                let method = if titles.contains_key(&normalized) {
+                    Some(if *header == normalized {
+                        MatchMethod::Exact
+                    } else {
+                        MatchMethod::Normalized
+                    })
+                } else if title_to_name_mapping.contains_key(&normalized) {
+                    let lowercase = normalized.to_lowercase();
+                    Some(
+                        if titles.keys().any(|title| title.to_lowercase() == lowercase) {
+                            MatchMethod::CaseInsensitive
+                        } else {
+                            MatchMethod::Alias
+                        },
+                    )
+                } else {
+                    None
+                };
+                HeaderMapping {
+                    header: header.clone(),
+                    field: title_to_name_mapping.get(&normalized).cloned(),
+                    normalized,
+                    method,
+                }
+            })
+            .collect();
+        return Ok(mappings);
+    }
+}
+
+/// Warning listing the headers that match no data dictionary field, `None` when all of them
+/// match. importer-lib converts the cells of such columns without a field schema, which can hide
+/// a typo in a header or a column missing from the dictionary.
+pub fn unmapped_headers_warning(mappings: &[HeaderMapping]) -> Option<String> {
+    let unmapped: Vec<&str> = mappings
+        .iter()
+        .filter(|mapping| mapping.field.is_none())
+        .map(|mapping| mapping.header.as_str())
+        .collect();
+    if unmapped.is_empty() {
+        return None;
+    }
+    return Some(format!(
+        "⚠️ {} columns match no data dictionary field and are converted without a field schema: {}",
+        unmapped.len(),
+        unmapped.join(", ")
+    ));
+}
+
+/// Write the header mappings as a JSON array
+pub fn write_mapping_report(mappings: &[HeaderMapping], path: &str) -> Result<(), anyhow::Error> {
+    let content = serde_json::to_string_pretty(mappings)?;
+    std::fs::write(path, content)
+        .map_err(|e| anyhow::anyhow!("Failed to write mapping report to {path}: {e}"))?;
+    return Ok(());
+}
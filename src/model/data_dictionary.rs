@@ -1,9 +1,66 @@
+use crate::cache::{CachedResponse, SchemaCache};
 use importer_lib::anyhow;
 use importer_lib::reqwest::blocking::Client;
+use importer_lib::reqwest::header::{ETAG, IF_NONE_MATCH};
+use importer_lib::reqwest::StatusCode;
 use importer_lib::serde_json;
 use importer_lib::serde_json::{json, Value};
 use importer_lib::utils::{normalize_string, write_error_to_log};
 use std::collections::HashMap;
+use std::str::FromStr;
+
+/// JSON Schema draft declared in the generated schema's `$schema`, which the validator uses to pick
+/// keyword semantics. Draft 4 is not offered because its boolean `exclusiveMinimum`/`exclusiveMaximum`
+/// are incompatible with the numeric bounds the conversion emits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonSchemaDraft {
+    #[default]
+    Draft7,
+    Draft201909,
+    Draft202012,
+}
+
+impl JsonSchemaDraft {
+    pub fn schema_uri(&self) -> &'static str {
+        match self {
+            JsonSchemaDraft::Draft7 => "http://json-schema.org/draft-07/schema#",
+            JsonSchemaDraft::Draft201909 => "https://json-schema.org/draft/2019-09/schema",
+            JsonSchemaDraft::Draft202012 => "https://json-schema.org/draft/2020-12/schema",
+        }
+    }
+}
+
+impl FromStr for JsonSchemaDraft {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "7" | "07" | "draft-07" | "draft7" => Ok(JsonSchemaDraft::Draft7),
+            "2019-09" | "draft-2019-09" => Ok(JsonSchemaDraft::Draft201909),
+            "2020-12" | "draft-2020-12" => Ok(JsonSchemaDraft::Draft202012),
+            _ => Err(anyhow::anyhow!(
+                "Unsupported JSON Schema draft '{value}'. Supported drafts: draft-07, 2019-09, 2020-12"
+            )),
+        }
+    }
+}
+
+/// Canonical 8-4-4-4-12 hexadecimal UUID shape, in either case
+pub const UUID_PATTERN: &str =
+    "^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$";
+
+/// True when `value` has the canonical 8-4-4-4-12 hexadecimal UUID shape, in either case
+pub fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// At least one non-whitespace character, required of required string fields
+pub const NON_BLANK_PATTERN: &str = r"\S";
 
 pub struct DataDictionary {
     pub id: String,
@@ -17,14 +74,20 @@ impl DataDictionary {
         base_url: &str,
         data_dictionary_id: &str,
         client: &Client,
+    ) -> Result<Self, importer_lib::anyhow::Error> {
+        Self::new_with_cache(base_url, data_dictionary_id, client, None)
+    }
+
+    /// Same as `new`, but revalidates a cached copy of the data dictionaries with `If-None-Match`
+    /// and reuses it when the server answers 304 Not Modified
+    pub fn new_with_cache(
+        base_url: &str,
+        data_dictionary_id: &str,
+        client: &Client,
+        cache: Option<&SchemaCache>,
     ) -> Result<Self, importer_lib::anyhow::Error> {
         let url = format!("{base_url}/api/1/metastore/schemas/data-dictionary/items");
-        let response = client
-            .get(&url)
-            .header("Accept", "application/json")
-            .header("Authorization", "Bearer <token>")
-            .send()?;
-        let body = response.text()?;
+        let body = Self::fetch_data_dictionaries(&url, client, cache)?;
 
         // Parse the response as an array of schema objects
         let schemas: Vec<Value> = serde_json::from_str(&body)?;
@@ -45,11 +108,6 @@ impl DataDictionary {
                 )
             })?;
 
-        // Extract the data portion and convert to JSON Schema format
-        let data = matching_schema
-            .get("data")
-            .ok_or_else(|| anyhow::anyhow!("Data dictionary data not found"))?;
-
         let data_dictionary_url = format!(
             "{base_url}/api/1/metastore/schemas/data-dictionary/items/{data_dictionary_id}"
         );
@@ -62,13 +120,23 @@ impl DataDictionary {
             ));
         }
 
+        return Self::from_metastore_item(&matching_schema, data_dictionary_url);
+    }
+
+    /// Build the data dictionary from a metastore item (`{"identifier": ..., "data": {...}}`)
+    fn from_metastore_item(item: &Value, url: String) -> Result<Self, anyhow::Error> {
+        // Extract the data portion and convert to JSON Schema format
+        let data = item
+            .get("data")
+            .ok_or_else(|| anyhow::anyhow!("Data dictionary data not found"))?;
+
         let normalized_fields = Self::normalize_field_data(data.clone())?;
 
         // Check for duplicate field names and titles after normalization
         Self::check_duplicates(&normalized_fields)?;
 
         return Ok(DataDictionary {
-            id: matching_schema
+            id: item
                 .get("identifier")
                 .and_then(|identifier| identifier.as_str())
                 .expect("Data dictionary identifier not found")
@@ -79,10 +147,51 @@ impl DataDictionary {
                 .expect("Data dictionary title not found")
                 .to_string(),
             fields: normalized_fields,
-            url: data_dictionary_url,
+            url,
         });
     }
 
+    fn fetch_data_dictionaries(
+        url: &str,
+        client: &Client,
+        cache: Option<&SchemaCache>,
+    ) -> Result<String, anyhow::Error> {
+        let cached = cache.and_then(|cache| cache.get(url));
+        let mut request = client
+            .get(url)
+            .header("Accept", "application/json")
+            .header("Authorization", "Bearer <token>");
+        if let Some(cached) = &cached {
+            request = request.header(IF_NONE_MATCH, &cached.etag);
+        }
+        let response = request.send()?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                return Ok(cached.body);
+            }
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(|etag| etag.to_string());
+        let etag = etag.filter(|_| response.status().is_success());
+        let body = response.text()?;
+        if let (Some(cache), Some(etag)) = (cache, etag) {
+            // A cache that cannot be written only costs a full download next time
+            let _ = cache.store(
+                url,
+                &CachedResponse {
+                    etag,
+                    body: body.clone(),
+                },
+            );
+        }
+        return Ok(body);
+    }
+
     /// Normalize field names and titles in the data dictionary structure
     /// This is done once during initialization to avoid repeated normalization
     fn normalize_field_data(mut data: Value) -> Result<Value, anyhow::Error> {
@@ -105,14 +214,32 @@ impl DataDictionary {
     }
 
     pub fn to_json_schema(&self) -> Result<Value, anyhow::Error> {
+        self.to_json_schema_with_draft(JsonSchemaDraft::default())
+    }
+
+    pub fn to_json_schema_with_draft(
+        &self,
+        draft: JsonSchemaDraft,
+    ) -> Result<Value, anyhow::Error> {
         // Use optimized version since self.fields is already normalized
-        Self::convert_data_dictionary_to_json_schema(&self.fields)
+        Self::convert_data_dictionary_to_json_schema_with_draft(&self.fields, draft)
     }
 
     /// Convert normalized data dictionary to JSON Schema (optimized version)
     /// This assumes field names and titles are already normalized
     pub fn convert_data_dictionary_to_json_schema(
         dkan_fields: &Value,
+    ) -> Result<Value, anyhow::Error> {
+        Self::convert_data_dictionary_to_json_schema_with_draft(
+            dkan_fields,
+            JsonSchemaDraft::default(),
+        )
+    }
+
+    /// Same as `convert_data_dictionary_to_json_schema`, declaring the given draft in `$schema`
+    pub fn convert_data_dictionary_to_json_schema_with_draft(
+        dkan_fields: &Value,
+        draft: JsonSchemaDraft,
     ) -> Result<Value, anyhow::Error> {
         let title = dkan_fields
             .get("title")
@@ -198,6 +325,13 @@ impl DataDictionary {
                 property.insert("description".to_string(), json!(description));
             }
 
+            // OpenAPI-style annotations are kept as-is, they don't affect validation
+            for annotation in ["readOnly", "writeOnly"] {
+                if let Some(flag) = field.get(annotation).and_then(|f| f.as_bool()) {
+                    property.insert(annotation.to_string(), json!(flag));
+                }
+            }
+
             // Special handling for datetime
             if field_type == "datetime" {
                 if let Some(format) = field.get("format").and_then(|f| f.as_str()) {
@@ -214,11 +348,21 @@ impl DataDictionary {
                 if format != "default" && !format.is_empty() {
                     property.insert("format".to_string(), json!(format));
                 }
+                // Draft-07 validators don't check the uuid format, so enforce its shape with a
+                // pattern. An explicit 'pattern' constraint still takes precedence.
+                if format == "uuid" {
+                    property.insert("pattern".to_string(), json!(UUID_PATTERN));
+                }
             }
 
             // Add field to required list if it's marked as required (either by constraints or asterisk in title)
             if will_be_required {
                 required_fields.push(schema_property_name.to_string());
+                // A blank cell read as an empty string would otherwise satisfy a required
+                // string field. An explicit 'minLength' constraint below still takes precedence.
+                if json_schema_type == "string" {
+                    property.insert("minLength".to_string(), json!(1));
+                }
             }
 
             // Add any additional constraints based on field properties
@@ -249,8 +393,46 @@ impl DataDictionary {
                     }
                 }
 
+                if let Some(exclusive_minimum) = constraints.get("exclusiveMinimum") {
+                    if let Some(min) = exclusive_minimum.as_f64() {
+                        property.insert("exclusiveMinimum".to_string(), json!(min));
+                    }
+                }
+
+                if let Some(exclusive_maximum) = constraints.get("exclusiveMaximum") {
+                    if let Some(max) = exclusive_maximum.as_f64() {
+                        property.insert("exclusiveMaximum".to_string(), json!(max));
+                    }
+                }
+
+                // jsonschema rejects a schema whose multipleOf isn't strictly positive
+                if let Some(multiple_of) = constraints.get("multipleOf") {
+                    match multiple_of.as_f64() {
+                        Some(step) if step > 0.0 => {
+                            property.insert("multipleOf".to_string(), multiple_of.clone());
+                        }
+                        _ => {
+                            return Err(anyhow::anyhow!(
+                                "Property '{}' has invalid 'multipleOf' {}: it must be a number greater than 0",
+                                schema_property_name,
+                                multiple_of
+                            ));
+                        }
+                    }
+                }
+
                 if let Some(pattern) = constraints.get("pattern") {
                     if let Some(pat) = pattern.as_str() {
+                        // Compile the pattern here (with the regex engine jsonschema uses) so a broken
+                        // regex names its field instead of failing later as an opaque schema error
+                        fancy_regex::Regex::new(pat).map_err(|e| {
+                            anyhow::anyhow!(
+                                "Property '{}' has invalid 'pattern' {:?}: {}",
+                                schema_property_name,
+                                pat,
+                                e
+                            )
+                        })?;
                         property.insert("pattern".to_string(), json!(pat));
                     }
                 }
@@ -258,6 +440,21 @@ impl DataDictionary {
                 if let Some(enum_values) = constraints.get("enum") {
                     property.insert("enum".to_string(), enum_values.clone());
                 }
+
+                // Pins the field to a single allowed value
+                if let Some(const_value) = constraints.get("const") {
+                    property.insert("const".to_string(), const_value.clone());
+                }
+            }
+
+            // A cell holding only spaces passes minLength, so required strings must also contain
+            // a non-whitespace character. A pattern of the dictionary is kept next to it in allOf.
+            if will_be_required && json_schema_type == "string" {
+                if property.contains_key("pattern") {
+                    property.insert("allOf".to_string(), json!([{"pattern": NON_BLANK_PATTERN}]));
+                } else {
+                    property.insert("pattern".to_string(), json!(NON_BLANK_PATTERN));
+                }
             }
 
             // Add default decimal constraints for numeric fields to prevent SQL syntax errors
@@ -272,23 +469,31 @@ impl DataDictionary {
                         property.insert("precision".to_string(), json!(20)); // 20 total digits
                     }
 
-                    // Ensure minimum/maximum are reasonable if not set
-                    if !property.contains_key("minimum") {
+                    // Ensure minimum/maximum are reasonable if not set (an exclusive bound counts as set)
+                    if !property.contains_key("minimum")
+                        && !property.contains_key("exclusiveMinimum")
+                    {
                         property.insert("minimum".to_string(), json!(-999999999.0));
                         // Reasonable min
                     }
-                    if !property.contains_key("maximum") {
+                    if !property.contains_key("maximum")
+                        && !property.contains_key("exclusiveMaximum")
+                    {
                         property.insert("maximum".to_string(), json!(999999999.0));
                         // Reasonable max
                     }
                 }
                 "integer" => {
                     // Ensure integer fields have reasonable bounds if not set
-                    if !property.contains_key("minimum") {
+                    if !property.contains_key("minimum")
+                        && !property.contains_key("exclusiveMinimum")
+                    {
                         property.insert("minimum".to_string(), json!(-2147483648));
                         // 32-bit int min
                     }
-                    if !property.contains_key("maximum") {
+                    if !property.contains_key("maximum")
+                        && !property.contains_key("exclusiveMaximum")
+                    {
                         property.insert("maximum".to_string(), json!(2147483647));
                         // 32-bit int max
                     }
@@ -301,10 +506,7 @@ impl DataDictionary {
 
         // Build the complete JSON Schema
         let mut json_schema = serde_json::Map::new();
-        json_schema.insert(
-            "$schema".to_string(),
-            json!("http://json-schema.org/draft-07/schema#"),
-        );
+        json_schema.insert("$schema".to_string(), json!(draft.schema_uri()));
         json_schema.insert("type".to_string(), json!("object"));
         json_schema.insert("title".to_string(), json!(title));
         json_schema.insert("properties".to_string(), Value::Object(properties));
@@ -319,6 +521,561 @@ impl DataDictionary {
         return Ok(Value::Object(json_schema));
     }
 
+    /// Also accept each field's machine name as its column header. Every titled field gets a
+    /// second property named after its machine name with the same constraints, mapped to itself
+    /// for the export. A required field must then have either header, so its entry in
+    /// `required` is replaced by an `anyOf` of the two in `allOf`.
+    pub fn add_machine_name_aliases(
+        json_schema: &mut Value,
+        title_to_name_mapping: &mut HashMap<String, String>,
+    ) -> Result<(), anyhow::Error> {
+        let mut aliases: Vec<(String, String)> = title_to_name_mapping
+            .iter()
+            .filter(|(title, name)| title != name)
+            .map(|(title, name)| (title.clone(), name.clone()))
+            .collect();
+        aliases.sort();
+
+        let properties = json_schema
+            .get("properties")
+            .and_then(|p| p.as_object())
+            .ok_or_else(|| anyhow::anyhow!("Properties not found in JSON Schema"))?;
+        for (title, name) in &aliases {
+            if properties.contains_key(name) {
+                return Err(anyhow::anyhow!(
+                    "Machine name '{}' of field '{}' is also the title of another field, \
+                    so it cannot be accepted as a column header",
+                    name,
+                    title
+                ));
+            }
+        }
+        Self::add_property_aliases(json_schema, title_to_name_mapping, &aliases)
+    }
+
+    /// Also accept the `aliases` listed on data dictionary fields (e.g. "Temp" and "Water Temp"
+    /// for "Temperature") as column headers, like `add_machine_name_aliases` does for machine
+    /// names. An alias shared by two fields, or equal to another field's title or name, is an error.
+    pub fn add_field_aliases(
+        json_schema: &mut Value,
+        dkan_fields: &Value,
+        title_to_name_mapping: &mut HashMap<String, String>,
+    ) -> Result<(), anyhow::Error> {
+        let fields = dkan_fields
+            .get("fields")
+            .and_then(|f| f.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Fields array not found in schema"))?;
+        // (property name, field name, aliases) of every field
+        let declared: Vec<(String, String, Vec<String>)> = fields
+            .iter()
+            .filter_map(|field| {
+                let name = normalize_string(field.get("name")?.as_str()?);
+                let title = field
+                    .get("title")
+                    .and_then(|t| t.as_str())
+                    .map(normalize_string)
+                    .unwrap_or_else(|| name.clone());
+                let aliases = field
+                    .get("aliases")
+                    .and_then(|a| a.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|alias| alias.as_str())
+                    .map(normalize_string)
+                    .filter(|alias| !alias.is_empty() && *alias != title)
+                    .collect();
+                Some((title, name, aliases))
+            })
+            .collect();
+
+        let mut aliases: Vec<(String, String)> = Vec::new();
+        for (title, name, field_aliases) in &declared {
+            for alias in field_aliases {
+                if let Some((other, _)) = aliases
+                    .iter()
+                    .find(|(other, a)| a == alias && other != title)
+                {
+                    return Err(anyhow::anyhow!(
+                        "Alias '{alias}' is declared by both fields '{other}' and '{title}'"
+                    ));
+                }
+                if let Some((other, _, _)) = declared.iter().find(|(other_title, other_name, _)| {
+                    other_title != title && (other_title == alias || other_name == alias)
+                }) {
+                    return Err(anyhow::anyhow!(
+                        "Alias '{alias}' of field '{title}' is the title or name of field '{other}'"
+                    ));
+                }
+                // Already accepted, e.g. the field's own machine name with --match-machine-names
+                if title_to_name_mapping.get(alias) == Some(name) {
+                    continue;
+                }
+                if !aliases.contains(&(title.clone(), alias.clone())) {
+                    aliases.push((title.clone(), alias.clone()));
+                }
+            }
+        }
+        Self::add_property_aliases(json_schema, title_to_name_mapping, &aliases)
+    }
+
+    /// Also accept the sheet headers that match a column header (title or alias) only when case
+    /// is ignored, e.g. "temperature" for "Temperature". Each such header becomes an alias of the
+    /// field. Headers differing only by case are ambiguous: two of them matching the same field,
+    /// or one matching several fields, is an error.
+    pub fn add_case_insensitive_aliases(
+        json_schema: &mut Value,
+        title_to_name_mapping: &mut HashMap<String, String>,
+        headers: &[String],
+    ) -> Result<(), anyhow::Error> {
+        let properties: Vec<String> = json_schema
+            .get("properties")
+            .and_then(|p| p.as_object())
+            .ok_or_else(|| anyhow::anyhow!("Properties not found in JSON Schema"))?
+            .keys()
+            .cloned()
+            .collect();
+        let field_of = |property: &str| {
+            title_to_name_mapping
+                .get(property)
+                .cloned()
+                .unwrap_or_else(|| property.to_string())
+        };
+
+        let mut aliases: Vec<(String, String)> = Vec::new();
+        // (field name, header) of every header, to find two headers for the same field
+        let mut matched: Vec<(String, &String)> = Vec::new();
+        for header in headers {
+            let lowercase = header.to_lowercase();
+            let mut candidates: Vec<&String> = properties
+                .iter()
+                .filter(|property| property.to_lowercase() == lowercase)
+                .collect();
+            if let Some(exact) = candidates.iter().find(|property| *property == &header) {
+                candidates = vec![*exact];
+            }
+            let mut fields: Vec<String> = candidates.iter().map(|p| field_of(p)).collect();
+            fields.sort();
+            fields.dedup();
+            let (property, field) = match (candidates.first(), fields.as_slice()) {
+                (None, _) => continue,
+                (Some(property), [field]) => (*property, field.clone()),
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Header '{header}' matches several columns when case is ignored: {}",
+                        candidates
+                            .iter()
+                            .map(|c| format!("'{c}'"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            };
+            if let Some((_, other)) = matched.iter().find(|(f, _)| *f == field) {
+                return Err(anyhow::anyhow!(
+                    "Headers '{other}' and '{header}' both match column '{property}' when case is ignored"
+                ));
+            }
+            matched.push((field, header));
+            if property != header {
+                aliases.push((property.clone(), header.clone()));
+            }
+        }
+        Self::add_property_aliases(json_schema, title_to_name_mapping, &aliases)
+    }
+
+    /// For each (title, alias), add a property named `alias` with the constraints of `title`,
+    /// mapped to the same field name for the export. Required titles are replaced by an `anyOf`
+    /// of all their headers in `allOf`, extending the one added by a previous call (`title` may
+    /// then be any header of that `anyOf`, e.g. an alias added earlier).
+    fn add_property_aliases(
+        json_schema: &mut Value,
+        title_to_name_mapping: &mut HashMap<String, String>,
+        aliases: &[(String, String)],
+    ) -> Result<(), anyhow::Error> {
+        let properties = json_schema
+            .get_mut("properties")
+            .and_then(|p| p.as_object_mut())
+            .ok_or_else(|| anyhow::anyhow!("Properties not found in JSON Schema"))?;
+        for (title, alias) in aliases {
+            if properties.contains_key(alias) {
+                return Err(anyhow::anyhow!(
+                    "Header '{alias}' of field '{title}' is already a schema property"
+                ));
+            }
+            if let Some(property) = properties.get(title).cloned() {
+                properties.insert(alias.clone(), property);
+            }
+        }
+
+        let mut alternatives: Vec<Value> = json_schema
+            .get("allOf")
+            .and_then(|a| a.as_array())
+            .cloned()
+            .unwrap_or_default();
+        if let Some(required) = json_schema
+            .get_mut("required")
+            .and_then(|r| r.as_array_mut())
+        {
+            required.retain(|entry| {
+                let is_aliased = aliases
+                    .iter()
+                    .any(|(title, _)| entry.as_str() == Some(title.as_str()));
+                if is_aliased {
+                    alternatives.push(json!({"anyOf": [{"required": [entry]}]}));
+                }
+                !is_aliased
+            });
+        }
+        for alternative in alternatives.iter_mut() {
+            let Some(options) = alternative.get_mut("anyOf").and_then(|a| a.as_array_mut()) else {
+                continue;
+            };
+            let headers: Vec<String> = options
+                .iter()
+                .filter_map(|option| option["required"][0].as_str())
+                .map(|header| header.to_string())
+                .collect();
+            for (_, alias) in aliases.iter().filter(|(t, _)| headers.contains(t)) {
+                options.push(json!({"required": [alias]}));
+            }
+        }
+        if !alternatives.is_empty() {
+            json_schema["allOf"] = Value::Array(alternatives);
+        }
+
+        for (title, alias) in aliases {
+            if let Some(name) = title_to_name_mapping.get(title).cloned() {
+                title_to_name_mapping.insert(alias.clone(), name);
+            }
+        }
+        return Ok(());
+    }
+
+    /// Check that every mapping key is a schema property and every mapping value is a field name
+    /// of the data dictionary, so a broken mapping fails early instead of producing wrong CSV headers
+    pub fn check_mapping_consistency(
+        json_schema: &Value,
+        dkan_fields: &Value,
+        title_to_name_mapping: &HashMap<String, String>,
+    ) -> Result<(), anyhow::Error> {
+        let properties = json_schema
+            .get("properties")
+            .and_then(|p| p.as_object())
+            .ok_or_else(|| anyhow::anyhow!("Properties not found in JSON Schema"))?;
+        let field_names: Vec<String> = dkan_fields
+            .get("fields")
+            .and_then(|f| f.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Fields array not found in schema"))?
+            .iter()
+            .filter_map(|field| field.get("name").and_then(|n| n.as_str()))
+            .map(normalize_string)
+            .collect();
+
+        let mut problems: Vec<String> = title_to_name_mapping
+            .iter()
+            .flat_map(|(title, name)| {
+                let mut problems = Vec::new();
+                if !properties.contains_key(title) {
+                    problems.push(format!("column '{title}' is not a schema property"));
+                }
+                if !field_names.contains(name) {
+                    problems.push(format!(
+                        "column '{title}' maps to '{name}', which is not a data dictionary field"
+                    ));
+                }
+                problems
+            })
+            .collect();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        problems.sort();
+        return Err(anyhow::anyhow!(
+            "Column mapping does not match the data dictionary:\n{}",
+            problems
+                .iter()
+                .map(|problem| format!("  • {problem}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ));
+    }
+
+    /// Required columns with no matching header, so a column missing from the whole sheet is
+    /// reported once instead of as a "required property" error on every row. Required fields
+    /// accepted under their machine name too (see `add_machine_name_aliases`) are reported as
+    /// "'Title' (or 'name')".
+    pub fn missing_required_columns(json_schema: &Value, headers: &[String]) -> Vec<String> {
+        let has_header = |column: &Value| {
+            column
+                .as_str()
+                .is_some_and(|column| headers.iter().any(|header| header == column))
+        };
+        let mut missing: Vec<String> = json_schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|column| !has_header(column))
+            .filter_map(|column| column.as_str().map(|c| format!("'{c}'")))
+            .collect();
+
+        let alternatives = json_schema
+            .get("allOf")
+            .and_then(|a| a.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|rule| rule.get("anyOf").and_then(|a| a.as_array()));
+        for alternative in alternatives {
+            let columns: Vec<&Value> = alternative
+                .iter()
+                .filter_map(|option| option.get("required").and_then(|r| r.as_array()))
+                .flatten()
+                .collect();
+            if !columns.iter().any(|column| has_header(column)) {
+                let names: Vec<String> = columns
+                    .iter()
+                    .filter_map(|column| column.as_str().map(|c| format!("'{c}'")))
+                    .collect();
+                if let Some((first, others)) = names.split_first() {
+                    missing.push(if others.is_empty() {
+                        first.clone()
+                    } else {
+                        format!("{first} (or {})", others.join(", "))
+                    });
+                }
+            }
+        }
+        missing
+    }
+
+    /// The headers under which the required columns appear in the sheet: required titles, and
+    /// the first header present of each required field accepted under several headers
+    pub fn required_headers(json_schema: &Value, headers: &[String]) -> Vec<String> {
+        let mut required: Vec<String> = json_schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|column| column.as_str())
+            .filter(|column| headers.iter().any(|header| header == column))
+            .map(|column| column.to_string())
+            .collect();
+        let alternatives = json_schema
+            .get("allOf")
+            .and_then(|a| a.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|rule| rule.get("anyOf").and_then(|a| a.as_array()));
+        for alternative in alternatives {
+            let present = alternative
+                .iter()
+                .filter_map(|option| option["required"][0].as_str())
+                .find(|column| headers.iter().any(|header| header == column));
+            if let Some(column) = present {
+                required.push(column.to_string());
+            }
+        }
+        required
+    }
+
+    /// Restrict validation to the given columns (schema property names, i.e. field titles).
+    /// The other properties accept any value and are no longer required, so their cells are
+    /// neither validated nor rejected. Leaving out a required column is an error unless
+    /// `allow_missing_required` is set.
+    pub fn restrict_schema_to_columns(
+        json_schema: &mut Value,
+        columns: &[String],
+        allow_missing_required: bool,
+    ) -> Result<(), anyhow::Error> {
+        let columns: Vec<String> = columns.iter().map(|c| normalize_string(c)).collect();
+        let properties = json_schema
+            .get_mut("properties")
+            .and_then(|p| p.as_object_mut())
+            .ok_or_else(|| anyhow::anyhow!("Properties not found in JSON Schema"))?;
+
+        let unknown: Vec<&String> = columns
+            .iter()
+            .filter(|column| !properties.contains_key(column.as_str()))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Columns not found in the data dictionary: {}",
+                unknown
+                    .iter()
+                    .map(|c| format!("'{c}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        for (name, property) in properties.iter_mut() {
+            if !columns.contains(name) {
+                *property = json!({});
+            }
+        }
+
+        if let Some(required) = json_schema
+            .get_mut("required")
+            .and_then(|r| r.as_array_mut())
+        {
+            let missing: Vec<String> = required
+                .iter()
+                .filter_map(|r| r.as_str())
+                .filter(|r| !columns.iter().any(|c| c == r))
+                .map(|r| format!("'{r}'"))
+                .collect();
+            if !missing.is_empty() && !allow_missing_required {
+                return Err(anyhow::anyhow!(
+                    "Required columns missing from the selected columns: {} (use --allow-missing-required to skip them)",
+                    missing.join(", ")
+                ));
+            }
+            required.retain(|r| r.as_str().is_some_and(|r| columns.iter().any(|c| c == r)));
+        }
+        return Ok(());
+    }
+
+    /// Validate the given columns (field titles) as strings whatever their declared type, so
+    /// ID-like values such as "007" keep their leading zeros instead of being coerced to numbers.
+    /// Numeric constraints are dropped and enum members are turned into strings.
+    pub fn force_string_properties(
+        json_schema: &mut Value,
+        columns: &[String],
+    ) -> Result<(), anyhow::Error> {
+        const NUMERIC_KEYWORDS: [&str; 7] = [
+            "minimum",
+            "maximum",
+            "exclusiveMinimum",
+            "exclusiveMaximum",
+            "multipleOf",
+            "decimalPlaces",
+            "precision",
+        ];
+        let properties = json_schema
+            .get_mut("properties")
+            .and_then(|p| p.as_object_mut())
+            .ok_or_else(|| anyhow::anyhow!("Properties not found in JSON Schema"))?;
+
+        for column in columns.iter().map(|c| normalize_string(c)) {
+            let property = properties
+                .get_mut(&column)
+                .and_then(|p| p.as_object_mut())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Column '{column}' not found in the data dictionary")
+                })?;
+            let nullable = matches!(property.get("type"), Some(Value::Array(types)) if types.iter().any(|t| t == "null"));
+            property.insert(
+                "type".to_string(),
+                if nullable {
+                    json!(["string", "null"])
+                } else {
+                    json!("string")
+                },
+            );
+            for keyword in NUMERIC_KEYWORDS {
+                property.remove(keyword);
+            }
+            if let Some(Value::Array(members)) = property.get_mut("enum") {
+                for member in members.iter_mut() {
+                    if !member.is_string() && !member.is_null() {
+                        *member = json!(member.to_string());
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    /// String members of the `enum` of each JSON Schema property that has one
+    pub fn enum_properties(json_schema: &Value) -> HashMap<String, Vec<String>> {
+        let Some(properties) = json_schema.get("properties").and_then(|p| p.as_object()) else {
+            return HashMap::new();
+        };
+        properties
+            .iter()
+            .filter_map(|(name, property)| {
+                let members: Vec<String> = property
+                    .get("enum")?
+                    .as_array()?
+                    .iter()
+                    .filter_map(|member| member.as_str().map(|m| m.to_string()))
+                    .collect();
+                (!members.is_empty()).then(|| (name.clone(), members))
+            })
+            .collect()
+    }
+
+    /// Names of the JSON Schema properties with `format: "uuid"`
+    pub fn uuid_properties(json_schema: &Value) -> Vec<String> {
+        let Some(properties) = json_schema.get("properties").and_then(|p| p.as_object()) else {
+            return Vec::new();
+        };
+        properties
+            .iter()
+            .filter(|(_, property)| property.get("format") == Some(&json!("uuid")))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Names of the JSON Schema properties holding dates: `format` "date" or "date-time", or a
+    /// DKAN datetime format
+    pub fn date_properties(json_schema: &Value) -> Vec<String> {
+        let Some(properties) = json_schema.get("properties").and_then(|p| p.as_object()) else {
+            return Vec::new();
+        };
+        properties
+            .iter()
+            .filter(|(_, property)| {
+                property.get("dkan_format").is_some()
+                    || [json!("date"), json!("date-time")]
+                        .iter()
+                        .any(|format| property.get("format") == Some(format))
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Names of the JSON Schema properties annotated with `readOnly: true`
+    pub fn read_only_properties(json_schema: &Value) -> Vec<String> {
+        let Some(properties) = json_schema.get("properties").and_then(|p| p.as_object()) else {
+            return Vec::new();
+        };
+        properties
+            .iter()
+            .filter(|(_, property)| property.get("readOnly") == Some(&json!(true)))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Names of the JSON Schema properties of type number (required or not)
+    pub fn number_properties(json_schema: &Value) -> Vec<String> {
+        Self::properties_of_type(json_schema, "number")
+    }
+
+    /// Names of the JSON Schema properties of type string (required or not)
+    pub fn string_properties(json_schema: &Value) -> Vec<String> {
+        Self::properties_of_type(json_schema, "string")
+    }
+
+    /// Names of the JSON Schema properties of type boolean (required or not)
+    pub fn boolean_properties(json_schema: &Value) -> Vec<String> {
+        Self::properties_of_type(json_schema, "boolean")
+    }
+
+    fn properties_of_type(json_schema: &Value, json_type: &str) -> Vec<String> {
+        let Some(properties) = json_schema.get("properties").and_then(|p| p.as_object()) else {
+            return Vec::new();
+        };
+        properties
+            .iter()
+            .filter(|(_, property)| match property.get("type") {
+                Some(Value::String(t)) => t == json_type,
+                Some(Value::Array(types)) => types.iter().any(|t| t == json_type),
+                _ => false,
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
     /// Create a mapping from normalized field titles to normalized field names
     /// This is a static method that can be easily unit tested
     pub fn create_title_to_name_mapping(
@@ -0,0 +1,118 @@
+use importer_lib::anyhow;
+use importer_lib::serde_json;
+use serde::Deserialize;
+
+/// A single import job: one Excel sheet validated against a data dictionary and
+/// uploaded as a distribution of a dataset
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ManifestEntry {
+    pub excel_file: String,
+    pub data_dictionary_id: String,
+    pub dataset_id: String,
+    /// Defaults to the first sheet of the workbook, whatever its name (e.g. "Hoja1")
+    #[serde(default)]
+    pub sheet_name: Option<String>,
+    /// Name of the uploaded file on DKAN, defaults to the generated local CSV name
+    #[serde(default)]
+    pub distribution_filename: Option<String>,
+}
+
+impl ManifestEntry {
+    /// Short human readable label used in the batch summary
+    pub fn label(&self) -> String {
+        format!(
+            "{} ({}) → dataset {}",
+            self.excel_file,
+            self.sheet_name.as_deref().unwrap_or("first sheet"),
+            self.dataset_id
+        )
+    }
+}
+
+/// A batch of import jobs, read from a JSON file containing an array of entries:
+/// `[{"excel_file": "...", "data_dictionary_id": "...", "dataset_id": "...", "sheet_name": "..."}]`
+#[derive(Debug)]
+pub struct Manifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn from_file(path: &str) -> Result<Self, anyhow::Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read manifest file {path}: {e}"))?;
+        Self::from_json(&content)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, anyhow::Error> {
+        let entries: Vec<ManifestEntry> =
+            serde_json::from_str(content).map_err(|e| anyhow::anyhow!("Invalid manifest: {e}"))?;
+        if entries.is_empty() {
+            return Err(anyhow::anyhow!("Manifest does not contain any entries"));
+        }
+        return Ok(Manifest { entries });
+    }
+
+    /// Run every entry in order, collecting the outcome of each one.
+    /// A failing entry does not stop the remaining entries from being processed.
+    pub fn run<F>(&self, mut run_entry: F) -> BatchSummary
+    where
+        F: FnMut(&ManifestEntry) -> Result<(), anyhow::Error>,
+    {
+        let outcomes = self
+            .entries
+            .iter()
+            .map(|entry| EntryOutcome {
+                entry: entry.clone(),
+                error: run_entry(entry).err().map(|e| e.to_string()),
+            })
+            .collect();
+        BatchSummary { outcomes }
+    }
+}
+
+#[derive(Debug)]
+pub struct EntryOutcome {
+    pub entry: ManifestEntry,
+    /// The error message if the entry failed, `None` on success
+    pub error: Option<String>,
+}
+
+/// Consolidated result of a manifest run
+#[derive(Debug)]
+pub struct BatchSummary {
+    pub outcomes: Vec<EntryOutcome>,
+}
+
+impl BatchSummary {
+    pub fn succeeded_count(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.error.is_none()).count()
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.outcomes.len() - self.succeeded_count()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.failed_count() > 0
+    }
+
+    pub fn report(&self) -> String {
+        let mut lines = vec![format!(
+            "📋 Manifest summary: {} of {} entries succeeded",
+            self.succeeded_count(),
+            self.outcomes.len()
+        )];
+        for (index, outcome) in self.outcomes.iter().enumerate() {
+            match &outcome.error {
+                None => lines.push(format!("  ✅ [{}] {}", index + 1, outcome.entry.label())),
+                Some(error) => lines.push(format!(
+                    "  ❌ [{}] {}: {}",
+                    index + 1,
+                    outcome.entry.label(),
+                    error
+                )),
+            }
+        }
+        lines.join("\n")
+    }
+}
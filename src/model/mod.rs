@@ -1,3 +1,5 @@
 pub mod data_dictionary;
+pub mod manifest;
 
-pub use data_dictionary::DataDictionary;
+pub use data_dictionary::{DataDictionary, JsonSchemaDraft};
+pub use manifest::{BatchSummary, Manifest, ManifestEntry};
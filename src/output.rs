@@ -0,0 +1,79 @@
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// Only errors are written
+    Quiet,
+    #[default]
+    Normal,
+    /// Also writes details about each step
+    Verbose,
+}
+
+/// Sink for every user-facing message, so the tool can be embedded and scripted cleanly.
+/// Errors are always written, progress messages are dropped in quiet mode and details
+/// are only written in verbose mode.
+pub struct Output {
+    verbosity: Verbosity,
+    out: RefCell<Box<dyn Write>>,
+    err: RefCell<Box<dyn Write>>,
+}
+
+impl Output {
+    pub fn new(verbosity: Verbosity, out: Box<dyn Write>, err: Box<dyn Write>) -> Self {
+        Output {
+            verbosity,
+            out: RefCell::new(out),
+            err: RefCell::new(err),
+        }
+    }
+
+    /// Output writing to the process' stdout and stderr
+    pub fn stdio(verbosity: Verbosity) -> Self {
+        Self::new(
+            verbosity,
+            Box::new(std::io::stdout()),
+            Box::new(std::io::stderr()),
+        )
+    }
+
+    /// Progress and success messages (stdout)
+    pub fn info(&self, message: impl Display) {
+        if self.verbosity >= Verbosity::Normal {
+            let _ = writeln!(self.out.borrow_mut(), "{message}");
+        }
+    }
+
+    /// Machine-readable results for scripts (stdout), written whatever the verbosity
+    pub fn result(&self, message: impl Display) {
+        let _ = writeln!(self.out.borrow_mut(), "{message}");
+    }
+
+    /// Extra details, only shown with --verbose (stdout)
+    pub fn detail(&self, message: impl Display) {
+        if self.verbosity >= Verbosity::Verbose {
+            let _ = writeln!(self.out.borrow_mut(), "{message}");
+        }
+    }
+
+    /// Non-fatal problems (stderr)
+    pub fn warn(&self, message: impl Display) {
+        if self.verbosity >= Verbosity::Normal {
+            let _ = writeln!(self.err.borrow_mut(), "{message}");
+        }
+    }
+
+    /// Errors are written whatever the verbosity (stderr)
+    pub fn error(&self, message: impl Display) {
+        let _ = writeln!(self.err.borrow_mut(), "{message}");
+    }
+
+    /// A validation that found errors, with a pointer to the log holding their details. Both
+    /// are errors, so the pointer is kept in quiet mode.
+    pub fn validation_failed(&self, error_count: usize, errors_log: &str) {
+        self.error(format!("❌ Validation failed with {error_count} errors"));
+        self.error(format!("❌ Check {errors_log} for details."));
+    }
+}
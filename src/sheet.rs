@@ -0,0 +1,268 @@
+use calamine::{Data, Reader};
+use importer_lib::anyhow;
+use importer_lib::utils::normalize_string;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// Cells of some columns of a sheet as text, for checks that span rows or sheets, which the
+/// row-by-row schema validation can't express
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetTable {
+    /// Normalized headers (first used row) of every column; blank header cells are empty strings
+    pub headers: Vec<String>,
+    /// Indices in `headers` of the columns that were read
+    columns: Vec<usize>,
+    /// (1-based row number in the sheet, values of the read columns) of each data row. Rows
+    /// with no value in any column, read or not, are left out.
+    pub rows: Vec<(usize, Vec<String>)>,
+}
+
+impl SheetTable {
+    /// Read the cells of `columns` (headers, compared once normalized). Columns missing from the
+    /// sheet are left out.
+    pub fn read(
+        excel_file: &str,
+        sheet_name: &str,
+        columns: &[String],
+    ) -> Result<Self, anyhow::Error> {
+        let open_error = |e: &dyn std::fmt::Display| {
+            anyhow::anyhow!("Failed to read sheet '{sheet_name}' in {excel_file}: {e}")
+        };
+        let mut workbook = calamine::open_workbook_auto(excel_file).map_err(|e| open_error(&e))?;
+        let range = workbook
+            .worksheet_range(sheet_name)
+            .map_err(|e| open_error(&e))?;
+        let first_row = range.start().map(|(row, _)| row as usize + 1).unwrap_or(1);
+
+        let mut rows = range.rows();
+        let headers: Vec<String> = rows
+            .next()
+            .unwrap_or_default()
+            .iter()
+            .map(|header| normalize_string(&header.to_string()))
+            .collect();
+        let read_columns: Vec<usize> = columns
+            .iter()
+            .filter_map(|column| {
+                let column = normalize_string(column);
+                headers.iter().position(|header| *header == column)
+            })
+            .collect();
+        let rows = rows
+            .enumerate()
+            .filter(|(_, cells)| !cells.iter().all(is_blank))
+            .map(|(index, cells)| {
+                let values = read_columns
+                    .iter()
+                    .map(|&column| {
+                        cells
+                            .get(column)
+                            .map(|cell| cell.to_string())
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                (first_row + index + 1, values)
+            })
+            .collect();
+        return Ok(SheetTable {
+            headers,
+            columns: read_columns,
+            rows,
+        });
+    }
+
+    /// Position in each row's values of the column whose header matches `column` once
+    /// normalized, if it was read
+    pub fn column_index(&self, column: &str) -> Option<usize> {
+        let column = normalize_string(column);
+        let header_index = self.headers.iter().position(|header| *header == column)?;
+        self.columns.iter().position(|&index| index == header_index)
+    }
+
+    /// (row number, trimmed value) of each non-blank cell of `column`, which must have been read
+    pub fn column_values(&self, column: &str) -> Result<Vec<(usize, &str)>, anyhow::Error> {
+        let index = self.column_index(column).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Column '{column}' not found, the sheet's headers are: {}",
+                self.headers
+                    .iter()
+                    .filter(|header| !header.is_empty())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+        return Ok(self
+            .rows
+            .iter()
+            .filter_map(|(row, cells)| Some((*row, cells.get(index)?.trim())))
+            .filter(|(_, value)| !value.is_empty())
+            .collect());
+    }
+}
+
+/// Empty cell, or text made only of whitespace
+fn is_blank(cell: &Data) -> bool {
+    match cell {
+        Data::Empty => true,
+        Data::String(text) => text.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// Cell of a required column that is empty or holds only whitespace
+#[derive(Debug, PartialEq)]
+pub struct BlankCell {
+    /// 1-based row number in the sheet
+    pub row: usize,
+    pub column: String,
+}
+
+impl SheetTable {
+    /// Blank cells of `columns` (normalized headers), row by row. Columns that weren't read are
+    /// skipped.
+    pub fn blank_cells(&self, columns: &[String]) -> Vec<BlankCell> {
+        let checked: Vec<(usize, &String)> = columns
+            .iter()
+            .filter_map(|column| Some((self.column_index(column)?, column)))
+            .collect();
+        let mut blank_cells = Vec::new();
+        for (row, cells) in &self.rows {
+            for &(index, column) in &checked {
+                if cells.get(index).is_none_or(|cell| cell.trim().is_empty()) {
+                    blank_cells.push(BlankCell {
+                        row: *row,
+                        column: column.clone(),
+                    });
+                }
+            }
+        }
+        blank_cells
+    }
+}
+
+/// Cell whose text is a formula, e.g. "=A1+B1", left in the sheet instead of its value
+#[derive(Debug, PartialEq)]
+pub struct FormulaCell {
+    /// 1-based row number in the sheet
+    pub row: usize,
+    pub column: String,
+    pub formula: String,
+}
+
+impl SheetTable {
+    /// Cells of `columns` (normalized headers) whose text starts with '=', row by row. Depending
+    /// on how the workbook was saved, calamine returns the formula rather than its cached value.
+    /// Columns that weren't read are skipped.
+    pub fn formula_cells(&self, columns: &[String]) -> Vec<FormulaCell> {
+        let checked: Vec<(usize, &String)> = columns
+            .iter()
+            .filter_map(|column| Some((self.column_index(column)?, column)))
+            .collect();
+        let mut formula_cells = Vec::new();
+        for (row, cells) in &self.rows {
+            for &(index, column) in &checked {
+                if let Some(cell) = cells.get(index).filter(|cell| cell.trim().starts_with('=')) {
+                    formula_cells.push(FormulaCell {
+                        row: *row,
+                        column: column.clone(),
+                        formula: cell.trim().to_string(),
+                    });
+                }
+            }
+        }
+        formula_cells
+    }
+}
+
+/// A column whose values must be keys of a column of another sheet, like a foreign key
+/// (--reference "Samples:Station ID=Stations:ID")
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetReference {
+    pub child_sheet: String,
+    pub child_column: String,
+    pub parent_sheet: String,
+    pub parent_column: String,
+}
+
+impl FromStr for SheetReference {
+    type Err = String;
+
+    /// Parse "CHILD_SHEET:CHILD_COLUMN=PARENT_SHEET:PARENT_COLUMN". Sheet names can't contain
+    /// ':' in Excel, so the first ':' of each side separates the sheet from the column.
+    fn from_str(definition: &str) -> Result<Self, Self::Err> {
+        let split = |side: &str| {
+            side.split_once(':')
+                .map(|(sheet, column)| (sheet.trim().to_string(), column.trim().to_string()))
+                .filter(|(sheet, column)| !sheet.is_empty() && !column.is_empty())
+        };
+        let parsed = definition
+            .split_once('=')
+            .and_then(|(child, parent)| Some((split(child)?, split(parent)?)));
+        match parsed {
+            Some(((child_sheet, child_column), (parent_sheet, parent_column))) => {
+                Ok(SheetReference {
+                    child_sheet,
+                    child_column,
+                    parent_sheet,
+                    parent_column,
+                })
+            }
+            None => Err(format!(
+                "Invalid reference '{definition}', expected CHILD_SHEET:CHILD_COLUMN=PARENT_SHEET:PARENT_COLUMN"
+            )),
+        }
+    }
+}
+
+/// Value of a child column with no matching key in the parent column
+#[derive(Debug, PartialEq)]
+pub struct BrokenReference {
+    /// 1-based row number in the child sheet
+    pub row: usize,
+    pub value: String,
+}
+
+impl SheetReference {
+    /// Child values (blank cells excepted) that are not a key of the parent column, compared as
+    /// trimmed text
+    pub fn check(&self, excel_file: &str) -> Result<Vec<BrokenReference>, anyhow::Error> {
+        let parent = SheetTable::read(
+            excel_file,
+            &self.parent_sheet,
+            std::slice::from_ref(&self.parent_column),
+        )?;
+        let keys: HashSet<&str> = parent
+            .column_values(&self.parent_column)
+            .map_err(|e| anyhow::anyhow!("Reference {self}: sheet '{}': {e}", self.parent_sheet))?
+            .into_iter()
+            .map(|(_, key)| key)
+            .collect();
+        let child = SheetTable::read(
+            excel_file,
+            &self.child_sheet,
+            std::slice::from_ref(&self.child_column),
+        )?;
+        let broken = child
+            .column_values(&self.child_column)
+            .map_err(|e| anyhow::anyhow!("Reference {self}: sheet '{}': {e}", self.child_sheet))?
+            .into_iter()
+            .filter(|(_, value)| !keys.contains(value))
+            .map(|(row, value)| BrokenReference {
+                row,
+                value: value.to_string(),
+            })
+            .collect();
+        return Ok(broken);
+    }
+}
+
+impl std::fmt::Display for SheetReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}={}:{}",
+            self.child_sheet, self.child_column, self.parent_sheet, self.parent_column
+        )
+    }
+}
@@ -0,0 +1,73 @@
+use crate::export::ColumnMaxLength;
+use crate::model::ManifestEntry;
+use crate::timing::RunTimings;
+use importer_lib::anyhow;
+use importer_lib::serde_json::{self, json, Value};
+use importer_lib::utils::get_local_datetime_with_format;
+
+/// Outcome of one import, written with --summary-json for dashboards and historical tracking
+#[derive(Debug, Default)]
+pub struct RunSummary {
+    pub excel_file: String,
+    pub sheet_name: String,
+    pub data_dictionary_id: String,
+    pub dataset_id: String,
+    /// Number of validation errors (0 when validation passed or was not reached)
+    pub error_count: usize,
+    /// Number of data rows in the sheet, `None` when the sheet was not read
+    pub total_rows: Option<usize>,
+    /// Rows with validation errors, one validation report each. `None` when validation didn't run.
+    pub invalid_rows: Option<usize>,
+    /// Why the import failed, `None` on success
+    pub error: Option<String>,
+    pub timings: RunTimings,
+    /// Longest value of each string column, `None` unless requested with --report-max-lengths
+    pub max_lengths: Option<Vec<ColumnMaxLength>>,
+}
+
+impl RunSummary {
+    pub fn new(entry: &ManifestEntry) -> Self {
+        RunSummary {
+            excel_file: entry.excel_file.clone(),
+            sheet_name: entry.sheet_name.clone().unwrap_or_default(),
+            data_dictionary_id: entry.data_dictionary_id.clone(),
+            dataset_id: entry.dataset_id.clone(),
+            ..Default::default()
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let seconds = |duration: Option<std::time::Duration>| duration.map(|d| d.as_secs_f64());
+        json!({
+            "excel_file": self.excel_file,
+            "sheet_name": self.sheet_name,
+            "data_dictionary_id": self.data_dictionary_id,
+            "dataset_id": self.dataset_id,
+            "succeeded": self.error.is_none(),
+            "error": self.error,
+            "error_count": self.error_count,
+            "total_rows": self.total_rows,
+            "valid_rows": self.total_rows.zip(self.invalid_rows).map(
+                |(total_rows, invalid_rows)| total_rows.saturating_sub(invalid_rows)
+            ),
+            "invalid_rows": self.invalid_rows,
+            // Rows are only counted once the CSV has been exported
+            "exported_rows": self.timings.export.map(|_| self.timings.row_count),
+            "uploaded_bytes": self.timings.upload.map(|_| self.timings.uploaded_bytes),
+            "timings": {
+                "validation_seconds": seconds(self.timings.validation),
+                "export_seconds": seconds(self.timings.export),
+                "upload_seconds": seconds(self.timings.upload),
+            },
+            "max_lengths": self.max_lengths,
+            "created_at": get_local_datetime_with_format("%Y-%m-%d %H:%M:%S"),
+        })
+    }
+
+    pub fn write(&self, path: &str) -> Result<(), anyhow::Error> {
+        let content = serde_json::to_string_pretty(&self.to_json())?;
+        std::fs::write(path, content)
+            .map_err(|e| anyhow::anyhow!("Failed to write run summary to {path}: {e}"))?;
+        return Ok(());
+    }
+}
@@ -0,0 +1,74 @@
+use importer_lib::anyhow;
+use importer_lib::serde_json::Value;
+use rust_xlsxwriter::{Format, Workbook};
+
+/// Sheet name of generated templates, the name Excel gives the first sheet of a new workbook
+pub const TEMPLATE_SHEET_NAME: &str = "Sheet1";
+
+/// One column of a blank template spreadsheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateColumn {
+    /// Field title as expected in the header row (asterisks of required fields kept),
+    /// or the field name when it has no title
+    pub header: String,
+    pub description: Option<String>,
+}
+
+impl TemplateColumn {
+    /// Columns of the data dictionary fields, in dictionary order
+    pub fn from_fields(dkan_fields: &Value) -> Result<Vec<Self>, anyhow::Error> {
+        let fields = dkan_fields
+            .get("fields")
+            .and_then(|f| f.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Fields array not found in schema"))?;
+
+        let mut columns = Vec::new();
+        for field in fields {
+            let header = field
+                .get("title")
+                .or_else(|| field.get("name"))
+                .and_then(|h| h.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Field name not found"))?;
+            columns.push(TemplateColumn {
+                header: header.to_string(),
+                description: field
+                    .get("description")
+                    .and_then(|d| d.as_str())
+                    .filter(|d| !d.is_empty())
+                    .map(|d| d.to_string()),
+            });
+        }
+        return Ok(columns);
+    }
+}
+
+/// Write a blank xlsx template with a header row of column titles and, when `with_descriptions`
+/// is set, a second row with the field descriptions. The header row is frozen.
+pub fn write_template(
+    path: &str,
+    columns: &[TemplateColumn],
+    with_descriptions: bool,
+) -> Result<(), anyhow::Error> {
+    let header_format = Format::new().set_bold();
+    let description_format = Format::new().set_italic().set_text_wrap();
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(TEMPLATE_SHEET_NAME)?;
+    for (index, column) in columns.iter().enumerate() {
+        let col = u16::try_from(index)
+            .map_err(|_| anyhow::anyhow!("Too many columns for a spreadsheet template"))?;
+        worksheet.write_string_with_format(0, col, &column.header, &header_format)?;
+        if with_descriptions {
+            if let Some(description) = &column.description {
+                worksheet.write_string_with_format(1, col, description, &description_format)?;
+            }
+        }
+        worksheet.set_column_width(col, column.header.chars().count().clamp(12, 40) as f64)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+    workbook
+        .save(path)
+        .map_err(|e| anyhow::anyhow!("Failed to write template {path}: {e}"))?;
+    return Ok(());
+}
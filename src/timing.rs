@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+/// Wall-clock timings of the stages of one import, printed as a summary at the end of a run
+#[derive(Debug, Default)]
+pub struct RunTimings {
+    /// Building the validator and validating every row (includes parsing the workbook)
+    pub validation: Option<Duration>,
+    pub export: Option<Duration>,
+    /// Uploading the CSV and updating the dataset distributions
+    pub upload: Option<Duration>,
+    pub row_count: usize,
+    pub uploaded_bytes: u64,
+}
+
+impl RunTimings {
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(validation) = self.validation {
+            let seconds = validation.as_secs_f64();
+            let mut part = format!(
+                "Validated {} rows in {:.1}s",
+                format_thousands(self.row_count),
+                seconds
+            );
+            if seconds > 0.0 {
+                part.push_str(&format!(
+                    " ({} rows/s)",
+                    format_thousands((self.row_count as f64 / seconds) as usize)
+                ));
+            }
+            parts.push(part);
+        }
+        if let Some(export) = self.export {
+            parts.push(format!("exported in {:.1}s", export.as_secs_f64()));
+        }
+        if let Some(upload) = self.upload {
+            parts.push(format!(
+                "uploaded {} in {:.1}s",
+                format_bytes(self.uploaded_bytes),
+                upload.as_secs_f64()
+            ));
+        }
+        parts.join("; ")
+    }
+}
+
+fn format_thousands(value: usize) -> String {
+    let digits = value.to_string();
+    let mut formatted = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    let bytes_f = bytes as f64;
+    if bytes_f >= MB {
+        format!("{:.1}MB", bytes_f / MB)
+    } else if bytes_f >= KB {
+        format!("{:.1}KB", bytes_f / KB)
+    } else {
+        format!("{bytes}B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_only_includes_measured_stages() {
+        let timings = RunTimings {
+            validation: Some(Duration::from_millis(1500)),
+            row_count: 3,
+            ..Default::default()
+        };
+        assert_eq!(timings.summary(), "Validated 3 rows in 1.5s (2 rows/s)");
+        assert_eq!(RunTimings::default().summary(), "");
+    }
+
+    #[test]
+    fn test_summary_line() {
+        let timings = RunTimings {
+            validation: Some(Duration::from_millis(4200)),
+            export: Some(Duration::from_millis(300)),
+            upload: Some(Duration::from_millis(6100)),
+            row_count: 120_000,
+            uploaded_bytes: 18 * 1024 * 1024,
+        };
+        assert_eq!(
+            timings.summary(),
+            "Validated 120,000 rows in 4.2s (28,571 rows/s); exported in 0.3s; uploaded 18.0MB in 6.1s"
+        );
+    }
+
+    #[test]
+    fn test_format_helpers() {
+        assert_eq!(format_thousands(0), "0");
+        assert_eq!(format_thousands(999), "999");
+        assert_eq!(format_thousands(1000), "1,000");
+        assert_eq!(format_thousands(1234567), "1,234,567");
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(2048), "2.0KB");
+    }
+}
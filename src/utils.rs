@@ -1,8 +1,14 @@
+use crate::output::Output;
+use calamine::{DataType, Reader};
 use importer_lib::anyhow;
 use importer_lib::reqwest::blocking::multipart::{Form, Part};
-use importer_lib::reqwest::blocking::Client;
+use importer_lib::reqwest::blocking::{Client, RequestBuilder, Response};
+use importer_lib::reqwest::StatusCode;
 use importer_lib::serde_json;
 use importer_lib::utils::{get_local_datetime_with_format, normalize_string};
+use serde::Serialize;
+use std::io::Read;
+use std::time::Duration;
 
 pub fn generate_unique_filename(dataset_id: &str, excel_sheet_name: &str) -> String {
     let timestamp = get_local_datetime_with_format("%Y-%m-%d_%H-%M-%S");
@@ -11,35 +17,154 @@ pub fn generate_unique_filename(dataset_id: &str, excel_sheet_name: &str) -> Str
     return filename.to_lowercase();
 }
 
-// Function to upload CSV to custom importer endpoint
+/// Name the CSV gets on DKAN: the requested distribution filename if one is given
+/// (a `.csv` extension is added when missing), otherwise the local file name
+pub fn resolve_distribution_filename(
+    distribution_filename: Option<&str>,
+    csv_path: &str,
+) -> Result<String, anyhow::Error> {
+    let Some(requested) = distribution_filename else {
+        let local_filename = std::path::Path::new(csv_path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("data.csv");
+        return Ok(local_filename.to_string());
+    };
+
+    let requested = requested.trim();
+    if requested.is_empty() || requested.contains('/') || requested.contains('\\') {
+        return Err(anyhow::anyhow!(
+            "Invalid distribution filename '{requested}': it must be a plain file name"
+        ));
+    }
+    if requested.to_lowercase().ends_with(".csv") {
+        return Ok(requested.to_string());
+    }
+    return Ok(format!("{requested}.csv"));
+}
+
+/// Default limit on the duration of each request to DKAN, so a hung instance fails the import
+/// instead of blocking it forever
+pub const DEFAULT_REQUEST_TIMEOUT_SECONDS: u64 = 30;
+
+/// HTTP client shared by every request to DKAN (data dictionary, dataset and upload endpoints).
+/// `timeout` covers a whole request, including sending the CSV body.
+pub fn build_client(timeout: Duration) -> Result<Client, anyhow::Error> {
+    Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create the HTTP client: {e}"))
+}
+
+/// How failed requests to DKAN are retried: connection errors and 5xx responses are retried up
+/// to `max_retries` times, waiting `base_delay`, then twice as long before each further attempt.
+/// Timeouts and 5xx responses other than 503 are only retried for idempotent methods such as
+/// GET, since a POST may still have been processed. 4xx responses are never retried.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (0-based)
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay.saturating_mul(2u32.saturating_pow(retry))
+    }
+
+    /// Send the request built by `build_request`, which is called again for every attempt so
+    /// that streamed bodies are recreated. The last response or error is returned as-is.
+    pub fn send<F>(
+        &self,
+        description: &str,
+        output: &Output,
+        mut build_request: F,
+    ) -> Result<Response, anyhow::Error>
+    where
+        F: FnMut() -> Result<RequestBuilder, anyhow::Error>,
+    {
+        let mut retry = 0;
+        loop {
+            let (client, request) = build_request()?.build_split();
+            let request = request?;
+            // A timed-out upload, or one answered with a 500 or 502, may have reached DKAN, and
+            // sending it again could add a duplicate distribution file. A 503 means the request
+            // was turned away before being processed.
+            let idempotent = request.method().is_idempotent();
+            let failure = match client.execute(request) {
+                Ok(response) if !retryable_status(response.status(), idempotent) => {
+                    return Ok(response)
+                }
+                Ok(response) if retry >= self.max_retries => return Ok(response),
+                Ok(response) => response.status().to_string(),
+                Err(e)
+                    if retry < self.max_retries
+                        && (e.is_connect() || (idempotent && e.is_timeout())) =>
+                {
+                    e.to_string()
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let delay = self.delay(retry);
+            retry += 1;
+            output.warn(format!(
+                "⚠️ {description} failed ({failure}), retrying in {:.1}s ({retry}/{})",
+                delay.as_secs_f64(),
+                self.max_retries
+            ));
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+/// 5xx responses after which a request is sent again. Only a 503 tells that a non-idempotent
+/// request was not processed.
+fn retryable_status(status: StatusCode, idempotent: bool) -> bool {
+    status.is_server_error() && (idempotent || status == StatusCode::SERVICE_UNAVAILABLE)
+}
+
+// Function to upload CSV to custom importer endpoint.
+// The file is streamed from disk instead of being loaded into memory. Its size is given up
+// front, so the request carries a Content-Length like a buffered upload and the endpoint never
+// sees a chunked body. The file is reopened for every retry.
 pub fn upload_distribution_csv_file(
     url: &str,
     csv_path: &str,
+    upload_filename: &str,
     username: &str,
     password: &str,
     client: &Client,
+    retry: &RetryPolicy,
+    output: &Output,
 ) -> Result<String, anyhow::Error> {
-    let csv_content = std::fs::read(csv_path)?;
-    let filename = std::path::Path::new(csv_path)
-        .file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or("data.csv");
-
-    // Create multipart form with the CSV file
-    let form = Form::new().part(
-        "csv",
-        Part::bytes(csv_content)
-            .file_name(filename.to_string())
-            .mime_str("text/csv")?,
-    );
-
     let upload_url = format!("{}/api/importer/upload", url);
 
-    let response = client
-        .post(&upload_url)
-        .basic_auth(username, Some(password))
-        .multipart(form)
-        .send()?;
+    let response = retry.send("CSV upload", output, || {
+        let csv_file = std::fs::File::open(csv_path)
+            .map_err(|e| anyhow::anyhow!("Failed to open CSV file {csv_path}: {e}"))?;
+        let csv_length = csv_file.metadata()?.len();
+
+        // Create multipart form with the CSV file
+        let form = Form::new().part(
+            "csv",
+            Part::reader_with_length(csv_file, csv_length)
+                .file_name(upload_filename.to_string())
+                .mime_str("text/csv")?,
+        );
+        Ok(client
+            .post(&upload_url)
+            .basic_auth(username, Some(password))
+            .multipart(form))
+    })?;
 
     let status = response.status();
 
@@ -62,6 +187,309 @@ pub fn upload_distribution_csv_file(
     }
 }
 
+/// Where an uploaded CSV ended up on DKAN, reported at the end of a run so CI can link to it
+#[derive(Debug, PartialEq, Serialize)]
+pub struct UploadedDistribution {
+    pub dataset_id: String,
+    pub dataset_url: String,
+    pub distribution_filename: String,
+    /// File URL returned by the DKAN importer upload endpoint
+    pub distribution_url: String,
+}
+
+impl UploadedDistribution {
+    pub fn new(
+        base_url: &str,
+        dataset_id: &str,
+        distribution_filename: &str,
+        distribution_url: &str,
+    ) -> Self {
+        UploadedDistribution {
+            dataset_id: dataset_id.to_string(),
+            dataset_url: format!("{base_url}/dataset/{dataset_id}"),
+            distribution_filename: distribution_filename.to_string(),
+            distribution_url: distribution_url.to_string(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize the uploaded distribution")
+    }
+}
+
+/// Write a generated JSON Schema to a file as pretty-printed JSON
+pub fn write_json_schema(json_schema: &serde_json::Value, path: &str) -> Result<(), anyhow::Error> {
+    let content = serde_json::to_string_pretty(json_schema)?;
+    std::fs::write(path, content)
+        .map_err(|e| anyhow::anyhow!("Failed to write JSON Schema to {path}: {e}"))?;
+    return Ok(());
+}
+
+/// Kind of content found at the start of a spreadsheet file
+#[derive(Debug, PartialEq)]
+pub enum SpreadsheetContent {
+    /// ZIP container, as used by xlsx, xlsm, xlsb and ods
+    Zip,
+    /// OLE compound document, as used by Excel 97-2003 xls
+    Ole,
+    /// Plain text, most likely a CSV saved with a spreadsheet extension
+    Text,
+    Empty,
+    Unknown,
+}
+
+impl SpreadsheetContent {
+    /// Classify a file from its first bytes
+    pub fn sniff(bytes: &[u8]) -> Self {
+        const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+        const OLE_MAGIC: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+        if bytes.is_empty() {
+            return SpreadsheetContent::Empty;
+        }
+        if bytes.starts_with(ZIP_MAGIC) {
+            return SpreadsheetContent::Zip;
+        }
+        if bytes.starts_with(OLE_MAGIC) {
+            return SpreadsheetContent::Ole;
+        }
+        // The prefix may end in the middle of a multi-byte character, which is still text
+        let is_utf8 = match std::str::from_utf8(bytes) {
+            Ok(_) => true,
+            Err(e) => e.error_len().is_none(),
+        };
+        if is_utf8 && !bytes.contains(&0) {
+            return SpreadsheetContent::Text;
+        }
+        return SpreadsheetContent::Unknown;
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            SpreadsheetContent::Zip => "a ZIP-based workbook (xlsx/xlsm/xlsb/ods)",
+            SpreadsheetContent::Ole => "a legacy Excel 97-2003 workbook (xls)",
+            SpreadsheetContent::Text => "plain text (probably CSV)",
+            SpreadsheetContent::Empty => "empty",
+            SpreadsheetContent::Unknown => "not a recognized spreadsheet format",
+        }
+    }
+}
+
+/// Check that the content of a spreadsheet matches its extension, so that a CSV renamed to
+/// .xlsx is reported clearly instead of failing deep inside the workbook parser.
+/// Files with other extensions are left for the parser to judge.
+pub fn check_spreadsheet_file(path: &str) -> Result<(), anyhow::Error> {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+    let expected = match extension.as_deref() {
+        Some("xlsx" | "xlsm" | "xlsb" | "xlam" | "ods") => SpreadsheetContent::Zip,
+        Some("xls" | "xla") => SpreadsheetContent::Ole,
+        _ => return Ok(()),
+    };
+
+    let mut header = Vec::with_capacity(512);
+    std::fs::File::open(path)
+        .and_then(|file| file.take(512).read_to_end(&mut header))
+        .map_err(|e| anyhow::anyhow!("Failed to read Excel file {path}: {e}"))?;
+
+    let content = SpreadsheetContent::sniff(&header);
+    if content != expected {
+        return Err(anyhow::anyhow!(
+            "File extension of {} says {} but the content is {}. Please save the file in the format matching its extension.",
+            path,
+            extension.unwrap_or_default(),
+            content.description()
+        ));
+    }
+    return Ok(());
+}
+
+/// Name of the sheet to validate: `sheet_name` when given, otherwise the sheet at the 0-based
+/// `sheet_index`, otherwise the first sheet of the workbook
+pub fn resolve_sheet_name(
+    excel_file: &str,
+    sheet_name: Option<&str>,
+    sheet_index: Option<usize>,
+) -> Result<String, anyhow::Error> {
+    if let Some(sheet_name) = sheet_name {
+        return Ok(sheet_name.to_string());
+    }
+    check_spreadsheet_file(excel_file)?;
+    let workbook = calamine::open_workbook_auto(excel_file)
+        .map_err(|e| anyhow::anyhow!("Failed to open Excel file {excel_file}: {e}"))?;
+    let sheet_names = workbook.sheet_names();
+    let index = sheet_index.unwrap_or(0);
+    match sheet_names.get(index) {
+        Some(sheet_name) => return Ok(sheet_name.clone()),
+        None if sheet_names.is_empty() => {
+            return Err(anyhow::anyhow!("Excel file {excel_file} has no sheets"));
+        }
+        None => {
+            return Err(anyhow::anyhow!(
+                "Sheet index {index} is out of range: {excel_file} has {} sheets ({}), indexes start at 0",
+                sheet_names.len(),
+                sheet_names.join(", ")
+            ));
+        }
+    }
+}
+
+/// Normalized headers (first used row) of a sheet, without blank cells
+pub fn read_sheet_headers(
+    excel_file: &str,
+    sheet_name: &str,
+) -> Result<Vec<String>, anyhow::Error> {
+    return Ok(read_raw_sheet_headers(excel_file, sheet_name)?
+        .iter()
+        .map(|header| normalize_string(header))
+        .filter(|header| !header.is_empty())
+        .collect());
+}
+
+/// Headers (first used row) of a sheet as written in the cells. Only the first row of xlsx/xlsm
+/// files is read; other formats are loaded through calamine's generic reader.
+pub fn read_raw_sheet_headers(
+    excel_file: &str,
+    sheet_name: &str,
+) -> Result<Vec<String>, anyhow::Error> {
+    let open_error = |e: &dyn std::fmt::Display| {
+        anyhow::anyhow!("Failed to read the headers of sheet '{sheet_name}' in {excel_file}: {e}")
+    };
+    let extension = std::path::Path::new(excel_file)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+
+    let mut headers = Vec::new();
+    if matches!(extension.as_deref(), Some("xlsx" | "xlsm")) {
+        let mut workbook: calamine::Xlsx<_> =
+            calamine::open_workbook(excel_file).map_err(|e| open_error(&e))?;
+        let mut cells = workbook
+            .worksheet_cells_reader(sheet_name)
+            .map_err(|e| open_error(&e))?;
+        let mut header_row = None;
+        while let Some(cell) = cells.next_cell().map_err(|e| open_error(&e))? {
+            let (row, _) = cell.get_position();
+            if *header_row.get_or_insert(row) != row {
+                break;
+            }
+            headers.push(cell.get_value().as_string().unwrap_or_default());
+        }
+    } else {
+        let mut workbook = calamine::open_workbook_auto(excel_file).map_err(|e| open_error(&e))?;
+        let range = workbook
+            .worksheet_range(sheet_name)
+            .map_err(|e| open_error(&e))?;
+        if let Some(row) = range.rows().next() {
+            headers.extend(row.iter().map(|cell| cell.as_string().unwrap_or_default()));
+        }
+    }
+    return Ok(headers);
+}
+
+/// Outcome of a DKAN connectivity and authentication check
+#[derive(Debug, PartialEq)]
+pub enum ConnectionStatus {
+    Connected,
+    /// The server answered but rejected the credentials (401/403)
+    AuthenticationFailed(StatusCode),
+    /// The server answered with another non-success status
+    HttpError(StatusCode),
+    /// The server could not be reached (DNS, refused connection, TLS, timeout...)
+    NetworkError(String),
+}
+
+impl ConnectionStatus {
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            status if status.is_success() => ConnectionStatus::Connected,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                ConnectionStatus::AuthenticationFailed(status)
+            }
+            status => ConnectionStatus::HttpError(status),
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        matches!(self, ConnectionStatus::Connected)
+    }
+
+    pub fn message(&self, url: &str) -> String {
+        match self {
+            ConnectionStatus::Connected => format!("✅ Connected and authenticated to {url}"),
+            ConnectionStatus::AuthenticationFailed(status) => format!(
+                "❌ Authentication failed at {url} ({status}). Please check the username and password"
+            ),
+            ConnectionStatus::HttpError(status) => {
+                format!("❌ {url} answered with an unexpected status: {status}")
+            }
+            ConnectionStatus::NetworkError(error) => format!("❌ Could not reach {url}: {error}"),
+        }
+    }
+}
+
+/// Fetch a lightweight metastore endpoint with the given credentials to check that the
+/// DKAN instance is reachable and accepts them
+pub fn check_connection(
+    url: &str,
+    username: &str,
+    password: &str,
+    client: &Client,
+) -> ConnectionStatus {
+    let endpoint_url = format!("{url}/api/1/metastore/schemas");
+    match client
+        .get(&endpoint_url)
+        .basic_auth(username, Some(password))
+        .send()
+    {
+        Ok(response) => ConnectionStatus::from_status(response.status()),
+        Err(e) => ConnectionStatus::NetworkError(e.to_string()),
+    }
+}
+
+/// Check that the dataset exists before anything is validated or uploaded,
+/// so that a wrong dataset id fails early with a clear message
+pub fn check_dataset_exists(
+    url: &str,
+    dataset_id: &str,
+    username: &str,
+    password: &str,
+    client: &Client,
+) -> Result<(), anyhow::Error> {
+    let endpoint_url = format!("{url}/api/1/metastore/schemas/dataset/items/{dataset_id}");
+    let response = client
+        .get(&endpoint_url)
+        .basic_auth(username, Some(password))
+        .send()?;
+
+    if response.status().is_success() {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(dataset_lookup_error_message(
+        response.status(),
+        dataset_id,
+        &endpoint_url
+    )))
+}
+
+fn dataset_lookup_error_message(
+    status: StatusCode,
+    dataset_id: &str,
+    endpoint_url: &str,
+) -> String {
+    match status {
+        StatusCode::NOT_FOUND => format!(
+            "Dataset {dataset_id} not found. Please check the --dataset-id value ({endpoint_url})"
+        ),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => format!(
+            "Not allowed to access dataset {dataset_id} ({status}). Please check the username and password"
+        ),
+        _ => format!("Failed to check the existence of dataset {dataset_id} ({status}) at {endpoint_url}"),
+    }
+}
+
 pub fn dataset_add_distribution(
     url: &str,
     dataset_id: &str,
@@ -71,13 +499,16 @@ pub fn dataset_add_distribution(
     username: &str,
     password: &str,
     client: &Client,
+    retry: &RetryPolicy,
+    output: &Output,
 ) -> Result<Option<String>, anyhow::Error> {
     // Step 1: Get the current dataset to ensure it exists and get its current state
     let endpoint_url = format!("{url}/api/1/metastore/schemas/dataset/items/{dataset_id}");
-    let get_response = client
-        .get(&endpoint_url)
-        .basic_auth(username, Some(password))
-        .send()?;
+    let get_response = retry.send("Dataset lookup", output, || {
+        Ok(client
+            .get(&endpoint_url)
+            .basic_auth(username, Some(password)))
+    })?;
 
     if !get_response.status().is_success() {
         let error_text = get_response.text()?;
@@ -146,22 +577,23 @@ pub fn dataset_add_distribution(
     dataset["distribution"] = serde_json::Value::Array(filtered_distributions);
 
     // Step 5: Update the dataset with the new distribution
-    let patch_response = client
-        .patch(&endpoint_url)
-        .basic_auth(username, Some(password))
-        .header("Content-Type", "application/json")
-        .json(&dataset)
-        .send()?;
+    let patch_response = retry.send("Dataset update", output, || {
+        Ok(client
+            .patch(&endpoint_url)
+            .basic_auth(username, Some(password))
+            .header("Content-Type", "application/json")
+            .json(&dataset))
+    })?;
 
     if patch_response.status().is_success() {
         if let Some(ref prev_filename) = previous_csv_filename {
-            println!("✅ Successfully replaced CSV distribution '{}' with '{}' in dataset \"{}\" with id \"{}\"",
-                prev_filename, file_name, dataset_title, dataset_id);
+            output.info(format!("✅ Successfully replaced CSV distribution '{}' with '{}' in dataset \"{}\" with id \"{}\"",
+                prev_filename, file_name, dataset_title, dataset_id));
         } else {
-            println!(
+            output.info(format!(
                 "✅ Successfully added CSV distribution '{}' to dataset \"{}\" with id \"{}\"",
                 file_name, dataset_title, dataset_id
-            );
+            ));
         }
         Ok(previous_csv_filename)
     } else {
@@ -181,13 +613,16 @@ pub fn delete_remote_file(
     username: &str,
     password: &str,
     client: &Client,
+    retry: &RetryPolicy,
+    output: &Output,
 ) -> Result<(), anyhow::Error> {
     let endpoint_url = format!("{url}/api/importer/delete/{file_name}");
-    let response = client
-        // The DELETE method is not supported for this endpoint, so we use POST instead
-        .post(&endpoint_url)
-        .basic_auth(username, Some(password))
-        .send()?;
+    let response = retry.send("Previous CSV deletion", output, || {
+        Ok(client
+            // The DELETE method is not supported for this endpoint, so we use POST instead
+            .post(&endpoint_url)
+            .basic_auth(username, Some(password)))
+    })?;
 
     if !response.status().is_success() {
         let error_text = response.text()?;
@@ -195,7 +630,9 @@ pub fn delete_remote_file(
             "Failed to delete file {file_name}: {error_text}"
         ));
     }
-    println!("🧹 Previous CSV file successfully deleted: {file_name}");
+    output.info(format!(
+        "🧹 Previous CSV file successfully deleted: {file_name}"
+    ));
     return Ok(());
 }
 
@@ -208,4 +645,142 @@ mod tests {
         assert!(filename.contains("test_sheet_with_spaces"));
         assert!(filename.ends_with(".csv"));
     }
+
+    #[test]
+    fn test_sniff_spreadsheet_content() {
+        use super::SpreadsheetContent;
+
+        assert_eq!(
+            SpreadsheetContent::sniff(b"PK\x03\x04\x14\x00"),
+            SpreadsheetContent::Zip
+        );
+        assert_eq!(
+            SpreadsheetContent::sniff(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1, 0x00]),
+            SpreadsheetContent::Ole
+        );
+        assert_eq!(
+            SpreadsheetContent::sniff("Sample ID,Température\nS1,12.5\n".as_bytes()),
+            SpreadsheetContent::Text
+        );
+        // Prefix cut in the middle of "é"
+        assert_eq!(
+            SpreadsheetContent::sniff(&"Temperatur é".as_bytes()[..12]),
+            SpreadsheetContent::Text
+        );
+        assert_eq!(SpreadsheetContent::sniff(b""), SpreadsheetContent::Empty);
+        assert_eq!(
+            SpreadsheetContent::sniff(&[0x00, 0xFF, 0xFE, 0x01]),
+            SpreadsheetContent::Unknown
+        );
+    }
+
+    #[test]
+    fn test_check_spreadsheet_file_reports_csv_renamed_to_xlsx() {
+        let path = std::env::temp_dir().join(format!("renamed_csv_{}.xlsx", std::process::id()));
+        std::fs::write(&path, "Sample ID,Temperature\nS1,12.5\n").unwrap();
+
+        let error = super::check_spreadsheet_file(path.to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(error.contains("says xlsx"));
+        assert!(error.contains("plain text (probably CSV)"));
+    }
+
+    #[test]
+    fn test_check_spreadsheet_file_accepts_matching_content() {
+        let dir = std::env::temp_dir();
+        let xlsx = dir.join(format!("matching_{}.xlsx", std::process::id()));
+        let xls = dir.join(format!("matching_{}.XLS", std::process::id()));
+        let xls_as_xlsx = dir.join(format!("legacy_{}.xlsx", std::process::id()));
+        let ole = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1, 0x00];
+        std::fs::write(&xlsx, b"PK\x03\x04 workbook").unwrap();
+        std::fs::write(&xls, ole).unwrap();
+        std::fs::write(&xls_as_xlsx, ole).unwrap();
+
+        let xlsx_result = super::check_spreadsheet_file(xlsx.to_str().unwrap());
+        let xls_result = super::check_spreadsheet_file(xls.to_str().unwrap());
+        let mismatch = super::check_spreadsheet_file(xls_as_xlsx.to_str().unwrap());
+        for path in [&xlsx, &xls, &xls_as_xlsx] {
+            let _ = std::fs::remove_file(path);
+        }
+
+        assert!(xlsx_result.is_ok());
+        assert!(xls_result.is_ok());
+        assert!(mismatch
+            .unwrap_err()
+            .to_string()
+            .contains("legacy Excel 97-2003 workbook"));
+    }
+
+    #[test]
+    fn test_connection_status_classification() {
+        use super::ConnectionStatus;
+        use importer_lib::reqwest::StatusCode;
+
+        assert_eq!(
+            ConnectionStatus::from_status(StatusCode::OK),
+            ConnectionStatus::Connected
+        );
+        assert_eq!(
+            ConnectionStatus::from_status(StatusCode::UNAUTHORIZED),
+            ConnectionStatus::AuthenticationFailed(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(
+            ConnectionStatus::from_status(StatusCode::FORBIDDEN),
+            ConnectionStatus::AuthenticationFailed(StatusCode::FORBIDDEN)
+        );
+        assert_eq!(
+            ConnectionStatus::from_status(StatusCode::SERVICE_UNAVAILABLE),
+            ConnectionStatus::HttpError(StatusCode::SERVICE_UNAVAILABLE)
+        );
+    }
+
+    #[test]
+    fn test_check_connection_refused_is_a_network_error() {
+        // Nothing listens on port 1 locally, so the connection is refused before any HTTP exchange
+        let client = importer_lib::reqwest::blocking::Client::new();
+        let status = super::check_connection("http://127.0.0.1:1", "user", "pass", &client);
+        assert!(matches!(status, super::ConnectionStatus::NetworkError(_)));
+        assert!(status
+            .message("http://127.0.0.1:1")
+            .starts_with("❌ Could not reach"));
+    }
+
+    #[test]
+    fn test_dataset_lookup_error_message() {
+        use importer_lib::reqwest::StatusCode;
+        let url = "https://dkan.example.com/api/1/metastore/schemas/dataset/items/abc";
+
+        let not_found = super::dataset_lookup_error_message(StatusCode::NOT_FOUND, "abc", url);
+        assert!(not_found.starts_with("Dataset abc not found"));
+
+        let unauthorized =
+            super::dataset_lookup_error_message(StatusCode::UNAUTHORIZED, "abc", url);
+        assert!(unauthorized.contains("username and password"));
+
+        let server_error = super::dataset_lookup_error_message(StatusCode::BAD_GATEWAY, "abc", url);
+        assert!(server_error.contains("502"));
+    }
+
+    #[test]
+    fn test_resolve_distribution_filename() {
+        let local = "./sample_2024-01-15_10-30-45_1234567890.csv";
+        assert_eq!(
+            super::resolve_distribution_filename(None, local).unwrap(),
+            "sample_2024-01-15_10-30-45_1234567890.csv"
+        );
+        assert_eq!(
+            super::resolve_distribution_filename(Some("north_adriatic_samples.csv"), local)
+                .unwrap(),
+            "north_adriatic_samples.csv"
+        );
+        assert_eq!(
+            super::resolve_distribution_filename(Some("north_adriatic_samples"), local).unwrap(),
+            "north_adriatic_samples.csv"
+        );
+        assert!(super::resolve_distribution_filename(Some("../escape.csv"), local).is_err());
+        assert!(super::resolve_distribution_filename(Some("  "), local).is_err());
+    }
 }
@@ -2,6 +2,10 @@ use importer_lib::serde_json::{json, Map, Value};
 use importer_lib::ExcelValidator;
 use importer_lib::ExcelValidatorBuilder;
 use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Creates a basic test schema for testing purposes
 #[allow(dead_code)]
@@ -154,3 +158,109 @@ pub fn create_test_title_to_name_mapping() -> HashMap<String, String> {
 
     mapping
 }
+
+/// Minimal HTTP server that answers incoming requests, in order, with canned responses
+/// and records every raw request it received (request line, headers and body)
+#[allow(dead_code)]
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+#[allow(dead_code)]
+impl MockServer {
+    pub fn start(responses: Vec<String>) -> MockServer {
+        Self::start_with_delay(responses, Duration::ZERO)
+    }
+
+    /// Same as `start`, but waits `delay` after reading each request before answering it
+    pub fn start_with_delay(responses: Vec<String>, delay: Duration) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+
+        std::thread::spawn(move || {
+            for response in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                let mut chunked = false;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap_or(0);
+                        }
+                        if name.eq_ignore_ascii_case("transfer-encoding")
+                            && value.trim().eq_ignore_ascii_case("chunked")
+                        {
+                            chunked = true;
+                        }
+                    }
+                    request.push_str(&line);
+                }
+                let mut body = vec![0; content_length];
+                let _ = reader.read_exact(&mut body);
+                if chunked {
+                    body = read_chunked_body(&mut reader);
+                }
+                request.push_str(&String::from_utf8_lossy(&body));
+                recorded.lock().unwrap().push(request);
+
+                std::thread::sleep(delay);
+                let mut stream = reader.into_inner();
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Reassemble a body sent with `Transfer-Encoding: chunked`
+fn read_chunked_body(reader: &mut impl BufRead) -> Vec<u8> {
+    let mut body = Vec::new();
+    loop {
+        let mut size_line = String::new();
+        if reader.read_line(&mut size_line).unwrap_or(0) == 0 {
+            break;
+        }
+        let size_hex = size_line.trim().split(';').next().unwrap_or_default();
+        let size = usize::from_str_radix(size_hex, 16).unwrap_or(0);
+        let mut chunk = vec![0; size + 2];
+        if reader.read_exact(&mut chunk).is_err() {
+            break;
+        }
+        if size == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..size]);
+    }
+    body
+}
+
+/// Raw HTTP response with the given status line (e.g. "200 OK"), extra headers and body
+#[allow(dead_code)]
+pub fn http_response(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+    let mut response = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+    response
+}
@@ -444,3 +444,59 @@ fn test_type_handling_with_preserved_asterisks() {
     assert!(!required.contains(&json!("Optional Number")));
     assert!(!required.contains(&json!("Optional Boolean")));
 }
+
+#[test]
+fn test_required_string_fields_reject_blank_values() {
+    let dkan_schema = json!({
+        "title": "Blank Required Test",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID *", "type": "string"},
+            {
+                "name": "station",
+                "title": "Station",
+                "type": "string",
+                "constraints": {"required": true, "minLength": 3}
+            },
+            {"name": "notes", "title": "Notes", "type": "string"},
+            {"name": "depth", "title": "Depth *", "type": "number"}
+        ]
+    });
+
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    let properties = &json_schema["properties"];
+
+    // Required strings are neither null, empty nor only whitespace
+    assert_eq!(properties["Sample ID*"]["type"], "string");
+    assert_eq!(properties["Sample ID*"]["minLength"], 1);
+    assert_eq!(properties["Sample ID*"]["pattern"], "\\S");
+    // An explicit minLength wins
+    assert_eq!(properties["Station"]["minLength"], 3);
+    // Optional strings and other types are unchanged
+    assert!(properties["Notes"].get("minLength").is_none());
+    assert!(properties["Notes"].get("pattern").is_none());
+    assert!(properties["Depth*"].get("minLength").is_none());
+    assert!(properties["Depth*"].get("pattern").is_none());
+}
+
+#[test]
+fn test_required_string_pattern_is_kept_next_to_the_non_blank_pattern() {
+    let dkan_schema = json!({
+        "title": "Blank Required Test",
+        "fields": [{
+            "name": "station",
+            "title": "Station *",
+            "type": "string",
+            "constraints": {"pattern": "^ST-[0-9]+$"}
+        }]
+    });
+
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    let station = &json_schema["properties"]["Station*"];
+
+    assert_eq!(station["pattern"], "^ST-[0-9]+$");
+    assert_eq!(station["allOf"], json!([{"pattern": "\\S"}]));
+}
@@ -0,0 +1,127 @@
+//! Tests for matching sheet headers to column titles ignoring case (--columns-case-insensitive)
+
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn fields() -> Value {
+    DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Water samples",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID", "type": "string"},
+            {"name": "temperature", "title": "Temperature*", "type": "number", "aliases": ["Temp"]}
+        ]
+    }))
+    .unwrap()
+}
+
+fn schema_and_mapping(fields: &Value) -> (Value, HashMap<String, String>) {
+    let mut json_schema = DataDictionary::convert_data_dictionary_to_json_schema(fields).unwrap();
+    let mut mapping = DataDictionary::create_title_to_name_mapping(fields).unwrap();
+    DataDictionary::add_field_aliases(&mut json_schema, fields, &mut mapping).unwrap();
+    (json_schema, mapping)
+}
+
+fn headers(headers: &[&str]) -> Vec<String> {
+    headers.iter().map(|header| header.to_string()).collect()
+}
+
+#[test]
+fn test_lowercased_header_maps_to_title_cased_field() {
+    let fields = fields();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&fields);
+    let headers = headers(&["sample id", "TEMPERATURE*"]);
+    DataDictionary::add_case_insensitive_aliases(&mut json_schema, &mut mapping, &headers).unwrap();
+
+    assert_eq!(mapping["sample id"], "sample_id");
+    assert_eq!(mapping["TEMPERATURE*"], "temperature");
+    assert_eq!(
+        json_schema["properties"]["sample id"],
+        json_schema["properties"]["Sample ID"]
+    );
+    // The required field is found under its lowercased header
+    assert_eq!(
+        json_schema["allOf"],
+        json!([{"anyOf": [
+            {"required": ["Temperature*"]},
+            {"required": ["Temp"]},
+            {"required": ["TEMPERATURE*"]}
+        ]}])
+    );
+    assert!(DataDictionary::missing_required_columns(&json_schema, &headers).is_empty());
+    DataDictionary::check_mapping_consistency(&json_schema, &fields, &mapping).unwrap();
+}
+
+#[test]
+fn test_header_matching_an_alias_ignoring_case() {
+    let fields = fields();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&fields);
+    DataDictionary::add_case_insensitive_aliases(
+        &mut json_schema,
+        &mut mapping,
+        &headers(&["temp"]),
+    )
+    .unwrap();
+
+    assert_eq!(mapping["temp"], "temperature");
+    let alternatives = json_schema["allOf"][0]["anyOf"].as_array().unwrap();
+    assert!(alternatives.contains(&json!({"required": ["temp"]})));
+}
+
+#[test]
+fn test_exact_headers_are_left_alone() {
+    let fields = fields();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&fields);
+    let before = json_schema.clone();
+    DataDictionary::add_case_insensitive_aliases(
+        &mut json_schema,
+        &mut mapping,
+        &headers(&["Sample ID", "Temperature*", "Notes"]),
+    )
+    .unwrap();
+
+    assert_eq!(json_schema, before);
+}
+
+#[test]
+fn test_two_headers_for_the_same_field_are_an_error() {
+    let fields = fields();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&fields);
+    let error = DataDictionary::add_case_insensitive_aliases(
+        &mut json_schema,
+        &mut mapping,
+        &headers(&["Sample ID", "sample id"]),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Headers 'Sample ID' and 'sample id' both match column 'Sample ID' when case is ignored"
+    );
+}
+
+#[test]
+fn test_titles_differing_only_by_case_make_the_header_ambiguous() {
+    let fields = DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Water samples",
+        "fields": [
+            {"name": "ph", "title": "PH", "type": "number"},
+            {"name": "ph_corrected", "title": "pH", "type": "number"}
+        ]
+    }))
+    .unwrap();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&fields);
+
+    // An exact header is not ambiguous
+    DataDictionary::add_case_insensitive_aliases(&mut json_schema, &mut mapping, &headers(&["pH"]))
+        .unwrap();
+    let error = DataDictionary::add_case_insensitive_aliases(
+        &mut json_schema,
+        &mut mapping,
+        &headers(&["ph"]),
+    )
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("Header 'ph' matches several columns when case is ignored"));
+}
@@ -0,0 +1,50 @@
+//! Tests for writing the generated JSON Schema to a file (--dump-schema)
+
+use dkan_importer::model::DataDictionary;
+use dkan_importer::utils::write_json_schema;
+use importer_lib::serde_json::{self, json, Value};
+
+#[test]
+fn test_dumped_schema_matches_in_memory_schema() {
+    let dkan_fields = json!({
+        "title": "Marine Samples",
+        "fields": [
+            {
+                "name": "sample_id",
+                "title": "Sample ID*",
+                "type": "string"
+            },
+            {
+                "name": "depth",
+                "title": "Depth (m)",
+                "type": "number",
+                "constraints": { "minimum": 0 }
+            }
+        ]
+    });
+    let normalized_fields = DataDictionary::normalize_field_data_for_tests(dkan_fields).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_fields).unwrap();
+
+    let path = std::env::temp_dir().join(format!("dump_schema_{}.json", std::process::id()));
+    let path = path.to_string_lossy().to_string();
+    write_json_schema(&json_schema, &path).unwrap();
+
+    let written: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(written, json_schema);
+    assert_eq!(
+        written["$schema"],
+        "http://json-schema.org/draft-07/schema#"
+    );
+    assert_eq!(written["required"], json!(["Sample ID*"]));
+}
+
+#[test]
+fn test_dump_schema_to_missing_directory_fails_with_path() {
+    let error = write_json_schema(&json!({}), "/nonexistent-directory/schema.json")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("/nonexistent-directory/schema.json"));
+}
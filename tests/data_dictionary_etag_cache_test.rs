@@ -0,0 +1,66 @@
+//! Tests for revalidating the cached data dictionaries with their ETag
+
+mod common;
+
+use common::{http_response, MockServer};
+use dkan_importer::cache::SchemaCache;
+use dkan_importer::model::DataDictionary;
+use importer_lib::reqwest::blocking::Client;
+use importer_lib::serde_json::json;
+
+const DICTIONARY_ID: &str = "12345678-1234-5678-9012-123456789012";
+
+fn data_dictionaries_body() -> String {
+    json!([{
+        "identifier": DICTIONARY_ID,
+        "data": {
+            "title": "Water samples",
+            "fields": [
+                {"name": "sample_id", "title": "Sample ID", "type": "string"}
+            ]
+        }
+    }])
+    .to_string()
+}
+
+#[test]
+fn test_not_modified_response_reuses_cached_data_dictionary() {
+    let dir = std::env::temp_dir().join(format!("schema_cache_304_{}", std::process::id()));
+    let cache = SchemaCache::new(&dir);
+    let server = MockServer::start(vec![
+        http_response("200 OK", &[("ETag", "\"v1\"")], &data_dictionaries_body()),
+        http_response("200 OK", &[], "{}"),
+        http_response("304 Not Modified", &[("ETag", "\"v1\"")], ""),
+        http_response("200 OK", &[], "{}"),
+    ]);
+    let client = Client::new();
+
+    let first =
+        DataDictionary::new_with_cache(&server.url, DICTIONARY_ID, &client, Some(&cache)).unwrap();
+    let second =
+        DataDictionary::new_with_cache(&server.url, DICTIONARY_ID, &client, Some(&cache)).unwrap();
+
+    assert_eq!(second.name, "Water samples");
+    assert_eq!(second.fields, first.fields);
+
+    let requests = server.requests();
+    assert!(!requests[0].to_lowercase().contains("if-none-match"));
+    assert!(requests[2].to_lowercase().contains("if-none-match: \"v1\""));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_without_cache_no_etag_is_sent() {
+    let server = MockServer::start(vec![
+        http_response("200 OK", &[("ETag", "\"v1\"")], &data_dictionaries_body()),
+        http_response("200 OK", &[], "{}"),
+    ]);
+
+    let data_dictionary = DataDictionary::new(&server.url, DICTIONARY_ID, &Client::new()).unwrap();
+
+    assert_eq!(data_dictionary.id, DICTIONARY_ID);
+    assert!(!server.requests()[0]
+        .to_lowercase()
+        .contains("if-none-match"));
+}
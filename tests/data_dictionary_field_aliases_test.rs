@@ -0,0 +1,136 @@
+//! Tests for accepting the `aliases` of data dictionary fields as column headers
+
+use dkan_importer::model::DataDictionary;
+use dkan_importer::utils::read_sheet_headers;
+use importer_lib::serde_json::{json, Value};
+use rust_xlsxwriter::Workbook;
+use std::collections::HashMap;
+
+fn fields() -> Value {
+    DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Water samples",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID", "type": "string"},
+            {
+                "name": "temperature",
+                "title": "Temperature*",
+                "type": "number",
+                "aliases": ["Temp", "Water Temp"]
+            }
+        ]
+    }))
+    .unwrap()
+}
+
+fn schema_and_mapping(fields: &Value) -> (Value, HashMap<String, String>) {
+    let json_schema = DataDictionary::convert_data_dictionary_to_json_schema(fields).unwrap();
+    let mapping = DataDictionary::create_title_to_name_mapping(fields).unwrap();
+    (json_schema, mapping)
+}
+
+#[test]
+fn test_aliases_become_properties_mapped_to_the_field() {
+    let fields = fields();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&fields);
+    DataDictionary::add_field_aliases(&mut json_schema, &fields, &mut mapping).unwrap();
+
+    let temperature = json_schema["properties"]["Temperature*"].clone();
+    assert_eq!(json_schema["properties"]["Temp"], temperature);
+    assert_eq!(json_schema["properties"]["Water Temp"], temperature);
+    assert_eq!(mapping["Temp"], "temperature");
+    assert_eq!(mapping["Water Temp"], "temperature");
+    // The required field can be provided under any of its headers
+    assert_eq!(json_schema["required"], json!([]));
+    assert_eq!(
+        json_schema["allOf"],
+        json!([{"anyOf": [
+            {"required": ["Temperature*"]},
+            {"required": ["Temp"]},
+            {"required": ["Water Temp"]}
+        ]}])
+    );
+    DataDictionary::check_mapping_consistency(&json_schema, &fields, &mapping).unwrap();
+}
+
+#[test]
+fn test_sheet_using_an_alias_has_its_required_column() {
+    let path = std::env::temp_dir().join(format!("field_aliases_{}.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Sample ID").unwrap();
+    worksheet.write_string(0, 1, "Water Temp").unwrap();
+    worksheet.write_string(1, 0, "S1").unwrap();
+    worksheet.write_number(1, 1, 14.5).unwrap();
+    workbook.save(&path).unwrap();
+    let headers = read_sheet_headers(path.to_str().unwrap(), "Sheet1").unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let fields = fields();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&fields);
+    let missing = DataDictionary::missing_required_columns(&json_schema, &headers);
+    assert_eq!(missing, vec!["'Temperature*'"]);
+
+    DataDictionary::add_field_aliases(&mut json_schema, &fields, &mut mapping).unwrap();
+    assert!(DataDictionary::missing_required_columns(&json_schema, &headers).is_empty());
+    // The column is exported under the field's machine name
+    assert_eq!(mapping[&headers[1]], "temperature");
+}
+
+#[test]
+fn test_aliases_combine_with_machine_names() {
+    let fields = fields();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&fields);
+    DataDictionary::add_machine_name_aliases(&mut json_schema, &mut mapping).unwrap();
+    DataDictionary::add_field_aliases(&mut json_schema, &fields, &mut mapping).unwrap();
+
+    assert_eq!(
+        json_schema["allOf"],
+        json!([{"anyOf": [
+            {"required": ["Temperature*"]},
+            {"required": ["temperature"]},
+            {"required": ["Temp"]},
+            {"required": ["Water Temp"]}
+        ]}])
+    );
+    assert_eq!(
+        DataDictionary::missing_required_columns(&json_schema, &["Sample ID".to_string()]),
+        vec!["'Temperature*' (or 'temperature', 'Temp', 'Water Temp')"]
+    );
+}
+
+#[test]
+fn test_colliding_aliases_are_rejected() {
+    let shared = DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Water samples",
+        "fields": [
+            {"name": "water_temperature", "title": "Water Temperature", "type": "number", "aliases": ["Temp"]},
+            {"name": "air_temperature", "title": "Air Temperature", "type": "number", "aliases": ["Temp"]}
+        ]
+    }))
+    .unwrap();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&shared);
+    let error = DataDictionary::add_field_aliases(&mut json_schema, &shared, &mut mapping)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        error,
+        "Alias 'Temp' is declared by both fields 'Water Temperature' and 'Air Temperature'"
+    );
+
+    let title_clash = DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Water samples",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID", "type": "string"},
+            {"name": "station", "title": "Station", "type": "string", "aliases": ["Sample ID"]}
+        ]
+    }))
+    .unwrap();
+    let (mut json_schema, mut mapping) = schema_and_mapping(&title_clash);
+    let error = DataDictionary::add_field_aliases(&mut json_schema, &title_clash, &mut mapping)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        error,
+        "Alias 'Sample ID' of field 'Station' is the title or name of field 'Sample ID'"
+    );
+}
@@ -0,0 +1,64 @@
+//! Tests for validating selected columns as strings (--force-string-columns)
+
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::{json, Value};
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "Station Code": {"type": "integer", "minimum": -2147483648, "maximum": 2147483647},
+            "Sample ID": {"type": ["number", "null"], "decimalPlaces": 12, "precision": 20, "minimum": 0},
+            "Grade": {"type": ["integer", "null"], "enum": [1, 2, 3]},
+            "Temperature": {"type": ["number", "null"], "minimum": -5}
+        },
+        "required": ["Station Code"]
+    })
+}
+
+#[test]
+fn test_integer_column_is_validated_as_string() {
+    let mut json_schema = schema();
+    DataDictionary::force_string_properties(
+        &mut json_schema,
+        &["Station Code".to_string(), "Sample ID".to_string()],
+    )
+    .unwrap();
+
+    // "007" stays a string instead of being coerced to the integer 7
+    assert_eq!(
+        json_schema["properties"]["Station Code"],
+        json!({"type": "string"})
+    );
+    assert_eq!(
+        json_schema["properties"]["Sample ID"],
+        json!({"type": ["string", "null"]})
+    );
+    // Other columns and the required list are untouched
+    assert_eq!(
+        json_schema["properties"]["Temperature"],
+        json!({"type": ["number", "null"], "minimum": -5})
+    );
+    assert_eq!(json_schema["required"], json!(["Station Code"]));
+}
+
+#[test]
+fn test_enum_members_become_strings() {
+    let mut json_schema = schema();
+    DataDictionary::force_string_properties(&mut json_schema, &["Grade".to_string()]).unwrap();
+
+    assert_eq!(
+        json_schema["properties"]["Grade"],
+        json!({"type": ["string", "null"], "enum": ["1", "2", "3"]})
+    );
+}
+
+#[test]
+fn test_unknown_column_is_rejected() {
+    let mut json_schema = schema();
+    let error = DataDictionary::force_string_properties(&mut json_schema, &["Code".to_string()])
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(error, "Column 'Code' not found in the data dictionary");
+}
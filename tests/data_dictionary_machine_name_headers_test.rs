@@ -0,0 +1,72 @@
+//! Tests for accepting a field's machine name as its column header (--match-machine-names)
+
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::json;
+
+fn converted() -> (
+    importer_lib::serde_json::Value,
+    std::collections::HashMap<String, String>,
+) {
+    let dkan_schema = json!({
+        "title": "Machine Name Test",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID", "type": "string", "constraints": {"required": true}},
+            {"name": "depth_m", "title": "Depth (m)", "type": "number", "constraints": {"minimum": 0}},
+            {"name": "notes", "type": "string"}
+        ]
+    });
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    let mapping = DataDictionary::create_title_to_name_mapping(&normalized_schema).unwrap();
+    (json_schema, mapping)
+}
+
+#[test]
+fn test_machine_name_header_maps_to_the_same_field() {
+    let (mut json_schema, mut mapping) = converted();
+
+    DataDictionary::add_machine_name_aliases(&mut json_schema, &mut mapping).unwrap();
+
+    let properties = &json_schema["properties"];
+    assert_eq!(properties["depth_m"], properties["Depth (m)"]);
+    assert_eq!(properties["sample_id"], properties["Sample ID"]);
+    assert_eq!(mapping["depth_m"], "depth_m");
+    assert_eq!(mapping["Depth (m)"], "depth_m");
+    // A field without title already uses its name
+    assert_eq!(mapping["notes"], "notes");
+    assert_eq!(properties.as_object().unwrap().len(), 5);
+}
+
+#[test]
+fn test_required_field_accepts_either_header() {
+    let (mut json_schema, mut mapping) = converted();
+
+    DataDictionary::add_machine_name_aliases(&mut json_schema, &mut mapping).unwrap();
+
+    assert_eq!(json_schema["required"], json!([]));
+    assert_eq!(
+        json_schema["allOf"],
+        json!([{"anyOf": [{"required": ["Sample ID"]}, {"required": ["sample_id"]}]}])
+    );
+}
+
+#[test]
+fn test_machine_name_colliding_with_another_title_is_rejected() {
+    let dkan_schema = json!({
+        "title": "Collision Test",
+        "fields": [
+            {"name": "depth", "title": "Depth (m)", "type": "number"},
+            {"name": "depth_ft", "title": "depth", "type": "number"}
+        ]
+    });
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let mut json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    let mut mapping = DataDictionary::create_title_to_name_mapping(&normalized_schema).unwrap();
+
+    let error = DataDictionary::add_machine_name_aliases(&mut json_schema, &mut mapping)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Machine name 'depth'"));
+}
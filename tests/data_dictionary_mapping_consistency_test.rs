@@ -0,0 +1,42 @@
+//! Tests for checking that the column mapping matches the schema and the data dictionary
+
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::json;
+
+fn dictionary() -> importer_lib::serde_json::Value {
+    DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Mapping Test",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID", "type": "string"},
+            {"name": "depth_m", "title": "Depth (m)", "type": "number"}
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_generated_mapping_is_consistent() {
+    let fields = dictionary();
+    let json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+    let mapping = DataDictionary::create_title_to_name_mapping(&fields).unwrap();
+
+    assert!(DataDictionary::check_mapping_consistency(&json_schema, &fields, &mapping).is_ok());
+}
+
+#[test]
+fn test_mapping_to_unknown_machine_name_is_reported() {
+    let fields = dictionary();
+    let json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+    let mut mapping = DataDictionary::create_title_to_name_mapping(&fields).unwrap();
+    mapping.insert("Depth (m)".to_string(), "depht_m".to_string());
+    mapping.insert("Salinity".to_string(), "salinity".to_string());
+
+    let error = DataDictionary::check_mapping_consistency(&json_schema, &fields, &mapping)
+        .unwrap_err()
+        .to_string();
+
+    assert!(error
+        .contains("column 'Depth (m)' maps to 'depht_m', which is not a data dictionary field"));
+    assert!(error.contains("column 'Salinity' is not a schema property"));
+    assert!(!error.contains("Sample ID"));
+}
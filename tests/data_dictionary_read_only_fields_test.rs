@@ -0,0 +1,44 @@
+//! Tests for readOnly/writeOnly field annotations and --exclude-read-only
+
+use dkan_importer::export::drop_csv_columns;
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::json;
+
+#[test]
+fn test_read_only_field_is_annotated_and_can_be_dropped_from_export() {
+    let dkan_schema = json!({
+        "title": "Read Only Test",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID", "type": "string"},
+            {"name": "record_id", "title": "Record ID", "type": "integer", "readOnly": true},
+            {"name": "api_token", "title": "API Token", "type": "string", "writeOnly": true}
+        ]
+    });
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    let mapping = DataDictionary::create_title_to_name_mapping(&normalized_schema).unwrap();
+
+    // Annotations are kept and don't change the type
+    assert_eq!(json_schema["properties"]["Record ID"]["readOnly"], true);
+    assert_eq!(
+        json_schema["properties"]["Record ID"]["type"],
+        json!(["integer", "null"])
+    );
+    assert_eq!(json_schema["properties"]["API Token"]["writeOnly"], true);
+    assert!(json_schema["properties"]["Sample ID"]
+        .get("readOnly")
+        .is_none());
+
+    let read_only = DataDictionary::read_only_properties(&json_schema);
+    assert_eq!(read_only, vec!["Record ID"]);
+
+    let path = std::env::temp_dir().join(format!("read_only_export_{}.csv", std::process::id()));
+    std::fs::write(&path, "sample_id,record_id,api_token\nS1,17,x\n").unwrap();
+    let columns: Vec<String> = read_only.iter().map(|t| mapping[t].clone()).collect();
+    drop_csv_columns(path.to_str().unwrap(), &columns, None).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(content, "sample_id,api_token\nS1,x\n");
+}
@@ -1,6 +1,7 @@
 //! Tests for DKAN schema conversion functionality
 
-use dkan_importer::model::DataDictionary;
+use dkan_importer::model::data_dictionary::UUID_PATTERN;
+use dkan_importer::model::{DataDictionary, JsonSchemaDraft};
 use importer_lib::serde_json::json;
 
 #[test]
@@ -117,3 +118,253 @@ fn test_dkan_schema_conversion_basic() {
     let required = &json_schema["required"];
     assert!(required.as_array().unwrap().contains(&json!("Name")));
 }
+
+#[test]
+fn test_exclusive_bounds_are_carried_into_schema() {
+    let dkan_schema = json!({
+        "title": "Exclusive Bounds Schema",
+        "fields": [
+            {
+                "name": "depth",
+                "type": "number",
+                "title": "Depth (m)",
+                "constraints": {
+                    "exclusiveMinimum": 0,
+                    "maximum": 11000
+                }
+            },
+            {
+                "name": "replicate",
+                "type": "integer",
+                "title": "Replicate",
+                "constraints": {
+                    "exclusiveMaximum": 10
+                }
+            }
+        ]
+    });
+
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    let properties = &json_schema["properties"];
+
+    // Integer-valued exclusive bounds are read as floats, like minimum/maximum
+    assert_eq!(properties["Depth (m)"]["exclusiveMinimum"], 0.0);
+    assert_eq!(properties["Depth (m)"]["maximum"], 11000.0);
+    assert_eq!(properties["Replicate"]["exclusiveMaximum"], 10.0);
+
+    // An exclusive bound replaces the default inclusive bound on the same side
+    assert!(properties["Depth (m)"].get("minimum").is_none());
+    assert!(properties["Replicate"].get("maximum").is_none());
+    assert_eq!(properties["Replicate"]["minimum"], -2147483648);
+}
+
+#[test]
+fn test_invalid_pattern_error_names_property_and_keyword() {
+    let dkan_schema = json!({
+        "title": "Broken Pattern Schema",
+        "fields": [
+            {
+                "name": "sample_code",
+                "type": "string",
+                "title": "Sample Code",
+                "constraints": { "pattern": "^[A-Z]{3}-\\d+$" }
+            },
+            {
+                "name": "depth",
+                "type": "string",
+                "title": "Depth (m)",
+                "constraints": { "pattern": "^([0-9]+$" }
+            }
+        ]
+    });
+
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let error = DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema)
+        .unwrap_err()
+        .to_string();
+
+    assert!(
+        error.starts_with("Property 'Depth (m)' has invalid 'pattern' \"^([0-9]+$\""),
+        "Unexpected error: {error}"
+    );
+}
+
+#[test]
+fn test_valid_pattern_is_kept() {
+    let dkan_schema = json!({
+        "title": "Pattern Schema",
+        "fields": [
+            {
+                "name": "sample_code",
+                "type": "string",
+                "title": "Sample Code",
+                "constraints": { "pattern": "^[A-Z]{3}-\\d+$" }
+            }
+        ]
+    });
+
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    assert_eq!(
+        json_schema["properties"]["Sample Code"]["pattern"],
+        "^[A-Z]{3}-\\d+$"
+    );
+}
+
+#[test]
+fn test_uuid_format_gets_uuid_pattern() {
+    let dkan_schema = json!({
+        "title": "UUID Schema",
+        "fields": [
+            {"name": "station_id", "type": "string", "title": "Station ID", "format": "uuid"},
+            {
+                "name": "sample_id",
+                "type": "string",
+                "title": "Sample ID",
+                "format": "uuid",
+                "constraints": { "pattern": "^[0-9a-f-]{36}$" }
+            }
+        ]
+    });
+
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    let station = &json_schema["properties"]["Station ID"];
+    assert_eq!(station["format"], "uuid");
+    assert_eq!(station["pattern"], UUID_PATTERN);
+
+    let uuid = fancy_regex::Regex::new(UUID_PATTERN).unwrap();
+    assert!(uuid
+        .is_match("12345678-1234-5678-9012-123456789abc")
+        .unwrap());
+    assert!(uuid
+        .is_match("12345678-1234-5678-9012-123456789ABC")
+        .unwrap());
+    assert!(!uuid.is_match("12345678-1234-5678-9012-12345678").unwrap());
+    assert!(!uuid.is_match("12345678123456789012123456789abc").unwrap());
+
+    // An explicit pattern constraint wins
+    assert_eq!(
+        json_schema["properties"]["Sample ID"]["pattern"],
+        "^[0-9a-f-]{36}$"
+    );
+}
+
+#[test]
+fn test_const_constraint_is_carried_into_schema() {
+    let dkan_schema = json!({
+        "title": "Const Schema",
+        "fields": [
+            {
+                "name": "country",
+                "type": "string",
+                "title": "Country",
+                "constraints": { "const": "HR" }
+            },
+            {
+                "name": "version",
+                "type": "integer",
+                "title": "Version",
+                "constraints": { "const": 2 }
+            },
+            {"name": "station", "type": "string", "title": "Station"}
+        ]
+    });
+
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+
+    assert_eq!(json_schema["properties"]["Country"]["const"], "HR");
+    assert_eq!(json_schema["properties"]["Version"]["const"], 2);
+    assert!(json_schema["properties"]["Station"].get("const").is_none());
+}
+
+#[test]
+fn test_multiple_of_constraint_is_carried_into_schema() {
+    let dkan_schema = json!({
+        "title": "Step Schema",
+        "fields": [
+            {
+                "name": "depth",
+                "type": "number",
+                "title": "Depth",
+                "constraints": { "multipleOf": 0.5 }
+            }
+        ]
+    });
+
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    assert_eq!(json_schema["properties"]["Depth"]["multipleOf"], 0.5);
+
+    let invalid = json!({
+        "title": "Step Schema",
+        "fields": [
+            {
+                "name": "depth",
+                "type": "number",
+                "title": "Depth",
+                "constraints": { "multipleOf": 0 }
+            }
+        ]
+    });
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(invalid).unwrap();
+    let error = DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Property 'Depth' has invalid 'multipleOf' 0"));
+}
+
+#[test]
+fn test_schema_declares_selected_draft() {
+    let dkan_schema = json!({
+        "title": "Draft Schema",
+        "fields": [
+            { "name": "email", "type": "string", "title": "Email", "format": "email" }
+        ]
+    });
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+
+    // Draft-07 stays the default
+    let default_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+    assert_eq!(
+        default_schema["$schema"],
+        "http://json-schema.org/draft-07/schema#"
+    );
+
+    let schema_2020 = DataDictionary::convert_data_dictionary_to_json_schema_with_draft(
+        &normalized_schema,
+        JsonSchemaDraft::Draft202012,
+    )
+    .unwrap();
+    assert_eq!(
+        schema_2020["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    // Only the declared draft changes, the properties are identical
+    assert_eq!(schema_2020["properties"], default_schema["properties"]);
+}
+
+#[test]
+fn test_json_schema_draft_parsing() {
+    assert_eq!(
+        "draft-07".parse::<JsonSchemaDraft>().unwrap(),
+        JsonSchemaDraft::Draft7
+    );
+    assert_eq!(
+        "2019-09".parse::<JsonSchemaDraft>().unwrap(),
+        JsonSchemaDraft::Draft201909
+    );
+    assert_eq!(
+        "2020-12".parse::<JsonSchemaDraft>().unwrap(),
+        JsonSchemaDraft::Draft202012
+    );
+    assert!("draft-04".parse::<JsonSchemaDraft>().is_err());
+}
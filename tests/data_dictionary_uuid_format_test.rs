@@ -0,0 +1,99 @@
+//! Tests for fields with `format: "uuid"`: shape check and lowercase export
+
+use dkan_importer::export::ExportOptions;
+use dkan_importer::model::data_dictionary::is_uuid;
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::json;
+
+#[test]
+fn test_valid_uuid() {
+    assert!(is_uuid("12345678-1234-5678-9012-123456789abc"));
+}
+
+#[test]
+fn test_uppercase_uuid_is_valid() {
+    assert!(is_uuid("12345678-1234-5678-9012-123456789ABC"));
+}
+
+#[test]
+fn test_malformed_uuids() {
+    assert!(!is_uuid("12345678-1234-5678-9012-12345678"));
+    assert!(!is_uuid("12345678123456789012123456789abc"));
+    assert!(!is_uuid("1234567g-1234-5678-9012-123456789abc"));
+    assert!(!is_uuid("12345678-1234-5678-9012-123456789abc-0000"));
+    assert!(!is_uuid(""));
+}
+
+#[test]
+fn test_uuid_properties() {
+    let dkan_schema = json!({
+        "title": "UUID Schema",
+        "fields": [
+            {"name": "station_id", "type": "string", "title": "Station ID", "format": "uuid"},
+            {"name": "name", "type": "string", "title": "Name"}
+        ]
+    });
+    let normalized_schema = DataDictionary::normalize_field_data_for_tests(dkan_schema).unwrap();
+    let json_schema =
+        DataDictionary::convert_data_dictionary_to_json_schema(&normalized_schema).unwrap();
+
+    assert_eq!(
+        DataDictionary::uuid_properties(&json_schema),
+        vec!["Station ID".to_string()]
+    );
+}
+
+#[test]
+fn test_uppercase_uuid_is_exported_in_lowercase() {
+    let path = std::env::temp_dir().join(format!("uuid_export_{}.csv", std::process::id()));
+    std::fs::write(
+        &path,
+        "station_id,name\n12345678-1234-5678-9012-123456789ABC,ABC\n,\n",
+    )
+    .unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let options = ExportOptions {
+        uuid_columns: vec!["station_id".to_string()],
+        ..Default::default()
+    };
+    options.apply(&path).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    // Only the uuid column is rewritten
+    assert_eq!(
+        content,
+        "station_id,name\n12345678-1234-5678-9012-123456789abc,ABC\n,\n"
+    );
+}
+
+#[test]
+fn test_uuid_columns_keep_the_export_format() {
+    let path = std::env::temp_dir().join(format!("uuid_export_crlf_{}.csv", std::process::id()));
+    let path = path.to_string_lossy().to_string();
+    let options = ExportOptions {
+        uuid_columns: vec!["station_id".to_string()],
+        ..Default::default()
+    };
+
+    // Lowercase UUIDs leave the file as exported
+    let lowercase = b"\"station_id\"\r\n\"12345678-1234-5678-9012-123456789abc\"\r\n";
+    std::fs::write(&path, lowercase).unwrap();
+    options.apply(&path).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), lowercase.to_vec());
+
+    // An uppercase UUID is rewritten with the same line endings
+    std::fs::write(
+        &path,
+        "station_id\r\n12345678-1234-5678-9012-123456789ABC\r\n",
+    )
+    .unwrap();
+    options.apply(&path).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        content,
+        "station_id\r\n12345678-1234-5678-9012-123456789abc\r\n"
+    );
+}
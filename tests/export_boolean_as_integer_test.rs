@@ -0,0 +1,53 @@
+//! Tests for exporting boolean fields as 1/0 (--boolean-as-integer)
+
+use dkan_importer::export::{boolean_as_integer, ExportOptions};
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::json;
+
+#[test]
+fn test_boolean_as_integer() {
+    assert_eq!(boolean_as_integer("true"), Some("1".to_string()));
+    assert_eq!(boolean_as_integer("FALSE"), Some("0".to_string()));
+    assert_eq!(boolean_as_integer(""), None);
+    assert_eq!(boolean_as_integer("yes"), None);
+}
+
+#[test]
+fn test_true_value_exports_as_one_when_enabled() {
+    let path = std::env::temp_dir().join(format!("boolean_as_integer_{}.csv", std::process::id()));
+    std::fs::write(
+        &path,
+        "sample_id,filtered,notes\nS1,true,true\nS2,false,\nS3,,x\n",
+    )
+    .unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let options = ExportOptions {
+        integer_booleans: vec!["filtered".to_string()],
+        ..Default::default()
+    };
+    options.apply(&path).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    // Only boolean columns change, empty cells stay empty
+    assert_eq!(
+        content,
+        "sample_id,filtered,notes\nS1,1,true\nS2,0,\nS3,,x\n"
+    );
+}
+
+#[test]
+fn test_boolean_properties() {
+    let json_schema = json!({
+        "properties": {
+            "Filtered": {"type": "boolean"},
+            "Frozen": {"type": ["boolean", "null"]},
+            "Notes": {"type": "string"}
+        }
+    });
+
+    let mut booleans = DataDictionary::boolean_properties(&json_schema);
+    booleans.sort();
+    assert_eq!(booleans, vec!["Filtered", "Frozen"]);
+}
@@ -0,0 +1,85 @@
+//! Tests for writing enum values with the dictionary's canonical casing in the exported CSV
+
+use dkan_importer::export::{canonical_enum_value, ExportOptions};
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::json;
+use std::collections::HashMap;
+
+fn members(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn test_canonical_enum_value() {
+    let status = members(&["Active", "Inactive"]);
+
+    assert_eq!(
+        canonical_enum_value(&status, "active"),
+        Some("Active".to_string())
+    );
+    assert_eq!(
+        canonical_enum_value(&status, "INACTIVE"),
+        Some("Inactive".to_string())
+    );
+    assert_eq!(canonical_enum_value(&status, "pending"), None);
+}
+
+#[test]
+fn test_lowercase_enum_value_is_exported_in_canonical_form() {
+    let path = std::env::temp_dir().join(format!("canonical_enum_{}.csv", std::process::id()));
+    std::fs::write(
+        &path,
+        "sample_id,status,notes\nS1,active,active\nS2,Inactive,\nS3,,x\n",
+    )
+    .unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let options = ExportOptions {
+        canonical_enums: HashMap::from([("status".to_string(), members(&["Active", "Inactive"]))]),
+        ..Default::default()
+    };
+    options.apply(&path).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    // Only the enum column is rewritten
+    assert_eq!(
+        content,
+        "sample_id,status,notes\nS1,Active,active\nS2,Inactive,\nS3,,x\n"
+    );
+}
+
+#[test]
+fn test_enum_properties_keep_string_members() {
+    let json_schema = json!({
+        "properties": {
+            "Status": {"type": ["string", "null"], "enum": ["Active", "Inactive", null]},
+            "Level": {"type": "integer", "enum": [1, 2, 3]},
+            "Sample ID": {"type": "string"}
+        }
+    });
+
+    let enums = DataDictionary::enum_properties(&json_schema);
+    assert_eq!(enums.len(), 1);
+    assert_eq!(enums["Status"], members(&["Active", "Inactive"]));
+}
+
+#[test]
+fn test_export_already_in_canonical_form_is_left_untouched() {
+    let path = std::env::temp_dir().join(format!("canonical_enum_kept_{}.csv", std::process::id()));
+    // Every field quoted, which a rewrite would not keep
+    let original = b"\"sample_id\",\"status\"\r\n\"S1\",\"Active\"\r\n\"S2\",\"\"\r\n";
+    std::fs::write(&path, original).unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let options = ExportOptions {
+        canonical_enums: HashMap::from([("status".to_string(), members(&["Active", "Inactive"]))]),
+        ..Default::default()
+    };
+    options.apply(&path).unwrap();
+
+    let content = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(content, original.to_vec());
+    assert!(!std::path::Path::new(&format!("{path}.tmp")).exists());
+}
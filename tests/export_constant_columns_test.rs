@@ -0,0 +1,47 @@
+//! Tests for appending constant columns (--constant) to the exported CSV
+
+use dkan_importer::export::{parse_constant_column, ExportOptions};
+
+#[test]
+fn test_parse_constant_column() {
+    assert_eq!(
+        parse_constant_column("source_system=LabA").unwrap(),
+        ("source_system".to_string(), "LabA".to_string())
+    );
+    assert_eq!(
+        parse_constant_column("query=a=b").unwrap(),
+        ("query".to_string(), "a=b".to_string())
+    );
+    assert_eq!(
+        parse_constant_column("note=").unwrap(),
+        ("note".to_string(), String::new())
+    );
+    assert!(parse_constant_column("source_system").is_err());
+    assert!(parse_constant_column("=LabA").is_err());
+}
+
+#[test]
+fn test_constant_columns_are_appended_to_every_row() {
+    let path = std::env::temp_dir().join(format!("constant_columns_{}.csv", std::process::id()));
+    std::fs::write(&path, "sample_id,temperature\nS1,12.5\nS2,13.0\nS3,\n").unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let options = ExportOptions {
+        constants: vec![
+            ("source_system".to_string(), "LabA".to_string()),
+            ("import_batch".to_string(), "2024Q1".to_string()),
+        ],
+        ..Default::default()
+    };
+    options.apply(&path).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        content,
+        "sample_id,temperature,source_system,import_batch\n\
+         S1,12.5,LabA,2024Q1\n\
+         S2,13.0,LabA,2024Q1\n\
+         S3,,LabA,2024Q1\n"
+    );
+}
@@ -0,0 +1,86 @@
+//! Tests for checking the dates of the exported CSV against --min-date and --max-date
+
+use dkan_importer::export::{check_date_range, parse_iso_date, DateRange, ExportDiscrepancy};
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::json;
+
+fn write_csv(name: &str, content: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{name}_{}.csv", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+fn date_range() -> DateRange {
+    DateRange {
+        min: Some(parse_iso_date("1950-01-01").unwrap()),
+        max: Some(parse_iso_date("2030-12-31").unwrap()),
+    }
+}
+
+#[test]
+fn test_date_before_min_date_is_out_of_range() {
+    let path = write_csv(
+        "date_range_min",
+        "sample_id,collected_at\nS1,2024-03-05T10:00:00\nS2,0202-03-05T10:00:00\nS3,\n",
+    );
+    let out_of_range =
+        check_date_range(&path, &["collected_at".to_string()], &date_range()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        out_of_range,
+        vec![ExportDiscrepancy {
+            row: 2,
+            column: "collected_at".to_string(),
+            value: "0202-03-05T10:00:00".to_string(),
+            reason: "is before the minimum date 1950-01-01".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_date_after_max_date_is_out_of_range() {
+    // Bounds are inclusive, and only date columns are checked
+    let path = write_csv(
+        "date_range_max",
+        "sample_id,collected_at\n2202-01-01,2030-12-31\nS2,2202-03-05\n",
+    );
+    let out_of_range =
+        check_date_range(&path, &["collected_at".to_string()], &date_range()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        out_of_range,
+        vec![ExportDiscrepancy {
+            row: 2,
+            column: "collected_at".to_string(),
+            value: "2202-03-05".to_string(),
+            reason: "is after the maximum date 2030-12-31".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_date_bounds_must_be_iso_dates() {
+    assert_eq!(parse_iso_date(" 2024-01-31 "), Ok("2024-01-31".to_string()));
+    assert!(parse_iso_date("31/01/2024").is_err());
+    assert!(parse_iso_date("2024-01-31T00:00:00").is_err());
+}
+
+#[test]
+fn test_datetime_fields_are_date_properties() {
+    let fields = DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Samples",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID", "type": "string"},
+            {"name": "collected_at", "title": "Collected at", "type": "datetime"}
+        ]
+    }))
+    .unwrap();
+    let json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+
+    assert_eq!(
+        DataDictionary::date_properties(&json_schema),
+        vec!["Collected at"]
+    );
+}
@@ -0,0 +1,69 @@
+//! Tests for detecting numeric values that don't fit DKAN's inferred DECIMAL column
+
+use dkan_importer::export::{find_precision_overflows, DecimalTarget, PrecisionWarning};
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::json;
+
+#[test]
+fn test_decimal_target_fits() {
+    let target = DecimalTarget::default();
+
+    assert!(target.fits("12.123456"));
+    assert!(target.fits("-0.5"));
+    // Trailing zeros are not lost
+    assert!(target.fits("1.50000000"));
+    assert!(target.fits("999999999999.1"));
+    assert!(target.fits("not a number"));
+    assert!(target.fits(""));
+
+    assert!(!target.fits("12.12345678"));
+    assert!(!target.fits("1234567890123.5"));
+}
+
+#[test]
+fn test_eight_decimals_are_flagged_under_scale_six() {
+    let path = std::env::temp_dir().join(format!("decimal_precision_{}.csv", std::process::id()));
+    std::fs::write(
+        &path,
+        "sample_id,depth_m,notes\nS1,12.5,0.123456789\nS2,3.14159265,ok\n",
+    )
+    .unwrap();
+
+    let warnings = find_precision_overflows(
+        path.to_str().unwrap(),
+        &["depth_m".to_string()],
+        DecimalTarget {
+            precision: 18,
+            scale: 6,
+        },
+    )
+    .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Only the number column is checked
+    assert_eq!(
+        warnings,
+        vec![PrecisionWarning {
+            row: 2,
+            column: "depth_m".to_string(),
+            value: "3.14159265".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_number_properties_include_optional_numbers_only() {
+    let json_schema = json!({
+        "properties": {
+            "Depth (m)": {"type": "number"},
+            "Temperature": {"type": ["number", "null"]},
+            "Count": {"type": "integer"},
+            "Sample ID": {"type": "string"},
+            "Ignored": {}
+        }
+    });
+
+    let mut numbers = DataDictionary::number_properties(&json_schema);
+    numbers.sort();
+    assert_eq!(numbers, vec!["Depth (m)", "Temperature"]);
+}
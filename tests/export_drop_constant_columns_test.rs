@@ -0,0 +1,42 @@
+//! Tests for detecting and dropping columns that hold the same value on every row
+//! (--drop-constant-columns)
+
+use dkan_importer::export::{drop_csv_columns, find_constant_columns};
+
+fn write_temp_csv(name: &str, content: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{}_{}.csv", name, std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_constant_optional_column_is_detected_and_dropped() {
+    let path = write_temp_csv(
+        "constant_value_columns",
+        "sample_id,station,notes,depth_m\nS1,TEMPLATE,,1.0\nS2,TEMPLATE,,2.5\nS3,TEMPLATE,,1.0\n",
+    );
+
+    let constant_columns = find_constant_columns(&path).unwrap();
+    // Empty columns are not reported
+    assert_eq!(
+        constant_columns,
+        vec![("station".to_string(), "TEMPLATE".to_string())]
+    );
+
+    drop_csv_columns(&path, &["station".to_string()], None).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        content,
+        "sample_id,notes,depth_m\nS1,,1.0\nS2,,2.5\nS3,,1.0\n"
+    );
+}
+
+#[test]
+fn test_single_row_has_no_constant_columns() {
+    let path = write_temp_csv("constant_value_single_row", "sample_id,station\nS1,North\n");
+
+    let constant_columns = find_constant_columns(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(constant_columns.is_empty());
+}
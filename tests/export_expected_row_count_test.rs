@@ -0,0 +1,42 @@
+//! Tests for the --expect-rows / --min-rows / --max-rows-expected safety check
+
+use dkan_importer::export::RowCountExpectation;
+
+#[test]
+fn test_exact_row_count_mismatch_is_reported() {
+    let expectation = RowCountExpectation {
+        exact: Some(120),
+        ..Default::default()
+    };
+
+    assert!(expectation.check(120).is_ok());
+    assert_eq!(
+        expectation.check(87).unwrap_err().to_string(),
+        "Expected exactly 120 data rows but the file has 87"
+    );
+}
+
+#[test]
+fn test_row_count_range() {
+    let expectation = RowCountExpectation {
+        min: Some(10),
+        max: Some(20),
+        ..Default::default()
+    };
+
+    assert!(expectation.check(10).is_ok());
+    assert!(expectation.check(20).is_ok());
+    assert_eq!(
+        expectation.check(9).unwrap_err().to_string(),
+        "Expected at least 10 data rows but the file has 9"
+    );
+    assert_eq!(
+        expectation.check(21).unwrap_err().to_string(),
+        "Expected at most 20 data rows but the file has 21"
+    );
+}
+
+#[test]
+fn test_no_expectation_accepts_any_count() {
+    assert!(RowCountExpectation::default().check(0).is_ok());
+}
@@ -0,0 +1,51 @@
+//! Tests for flattening multi-line cell values in the exported CSV
+
+use dkan_importer::export::{flatten_newlines, ExportOptions};
+
+fn write_temp_csv(name: &str, content: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{}_{}.csv", name, std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_flatten_newlines_handles_all_line_break_styles() {
+    assert_eq!(flatten_newlines("a\nb", " "), "a b");
+    assert_eq!(flatten_newlines("a\r\nb", " "), "a b");
+    assert_eq!(flatten_newlines("a\rb", " "), "a b");
+    assert_eq!(flatten_newlines("a\n\nb", " | "), "a |  | b");
+    assert_eq!(flatten_newlines("no breaks", " "), "no breaks");
+}
+
+#[test]
+fn test_two_line_notes_value_becomes_single_line_when_enabled() {
+    let original = "sample_id,notes\nS1,\"First line\nSecond line\"\nS2,plain\n";
+    let path = write_temp_csv("flatten_enabled", original);
+
+    let options = ExportOptions {
+        flatten_newlines: Some(" ".to_string()),
+        ..Default::default()
+    };
+    options.apply(&path).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        content,
+        "sample_id,notes\nS1,First line Second line\nS2,plain\n"
+    );
+}
+
+#[test]
+fn test_csv_is_untouched_when_disabled() {
+    let original = "sample_id,notes\nS1,\"First line\nSecond line\"\n";
+    let path = write_temp_csv("flatten_disabled", original);
+
+    let options = ExportOptions::default();
+    assert!(options.is_noop());
+    options.apply(&path).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(content, original);
+}
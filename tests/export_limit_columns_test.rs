@@ -0,0 +1,94 @@
+//! Tests for validating and exporting only a subset of columns (--columns)
+
+use dkan_importer::export::ExportOptions;
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::{json, Value};
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "Sample ID": {"type": "string", "minLength": 1},
+            "Temperature": {"type": "number", "minimum": -5.0},
+            "Notes": {"type": ["string", "null"]}
+        },
+        "required": ["Sample ID"],
+        "additionalProperties": false
+    })
+}
+
+fn columns(titles: &[&str]) -> Vec<String> {
+    titles.iter().map(|title| title.to_string()).collect()
+}
+
+#[test]
+fn test_unselected_columns_are_not_validated() {
+    let mut json_schema = schema();
+    DataDictionary::restrict_schema_to_columns(
+        &mut json_schema,
+        &columns(&["Sample ID", "Temperature"]),
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(json_schema["properties"]["Notes"], json!({}));
+    assert_eq!(json_schema["properties"]["Temperature"]["minimum"], -5.0);
+    assert_eq!(json_schema["required"], json!(["Sample ID"]));
+    assert_eq!(json_schema["additionalProperties"], false);
+}
+
+#[test]
+fn test_leaving_out_required_column_needs_allow_missing_required() {
+    let mut json_schema = schema();
+    let error = DataDictionary::restrict_schema_to_columns(
+        &mut json_schema,
+        &columns(&["Temperature"]),
+        false,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(error.contains("'Sample ID'"));
+    assert!(error.contains("--allow-missing-required"));
+
+    let mut json_schema = schema();
+    DataDictionary::restrict_schema_to_columns(&mut json_schema, &columns(&["Temperature"]), true)
+        .unwrap();
+    assert_eq!(json_schema["required"], json!([]));
+    assert_eq!(json_schema["properties"]["Sample ID"], json!({}));
+}
+
+#[test]
+fn test_unknown_column_is_rejected() {
+    let mut json_schema = schema();
+    let error = DataDictionary::restrict_schema_to_columns(
+        &mut json_schema,
+        &columns(&["Sample ID", "Salinity"]),
+        false,
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(error.contains("'Salinity'"));
+}
+
+#[test]
+fn test_export_keeps_only_selected_columns() {
+    let path = std::env::temp_dir().join(format!("limit_columns_{}.csv", std::process::id()));
+    std::fs::write(
+        &path,
+        "sample_id,temperature,notes\nS1,12.5,first\nS2,13.0,\"multi\nline\"\n",
+    )
+    .unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let options = ExportOptions {
+        columns: Some(columns(&["temperature", "sample_id"])),
+        ..Default::default()
+    };
+    options.apply(&path).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    // Original column order is kept
+    assert_eq!(content, "sample_id,temperature\nS1,12.5\nS2,13.0\n");
+}
@@ -0,0 +1,97 @@
+//! Tests for the --line-terminator option of the exported CSV
+
+use dkan_importer::export::{drop_csv_columns, ExportOptions, LineTerminator};
+use std::collections::HashMap;
+
+fn export_with(name: &str, line_terminator: LineTerminator) -> Vec<u8> {
+    let path = std::env::temp_dir().join(format!("{}_{}.csv", name, std::process::id()));
+    std::fs::write(
+        &path,
+        "sample_id,notes\r\nS1,\"two\nlines\"\r\nS2,plain\r\n",
+    )
+    .unwrap();
+
+    let options = ExportOptions {
+        line_terminator: Some(line_terminator),
+        ..Default::default()
+    };
+    options.apply(path.to_str().unwrap()).unwrap();
+
+    let content = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    content
+}
+
+#[test]
+fn test_crlf_terminator() {
+    let content = export_with("line_terminator_crlf", LineTerminator::Crlf);
+    // Line breaks inside quoted values are data and stay as they are
+    assert_eq!(
+        content,
+        b"sample_id,notes\r\nS1,\"two\nlines\"\r\nS2,plain\r\n".to_vec()
+    );
+}
+
+#[test]
+fn test_lf_terminator() {
+    let content = export_with("line_terminator_lf", LineTerminator::Lf);
+    assert_eq!(
+        content,
+        b"sample_id,notes\nS1,\"two\nlines\"\nS2,plain\n".to_vec()
+    );
+}
+
+#[test]
+fn test_crlf_is_kept_when_a_column_is_dropped() {
+    let path =
+        std::env::temp_dir().join(format!("line_terminator_drop_{}.csv", std::process::id()));
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "sample_id,station,notes\nS1,A,x\nS2,A,y\n").unwrap();
+
+    // Same order as an import: export options first, then read-only or constant columns dropped
+    let options = ExportOptions {
+        line_terminator: Some(LineTerminator::Crlf),
+        ..Default::default()
+    };
+    options.apply(path).unwrap();
+    drop_csv_columns(path, &["station".to_string()], options.line_terminator).unwrap();
+
+    let content = std::fs::read(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(content, b"sample_id,notes\r\nS1,x\r\nS2,y\r\n".to_vec());
+}
+
+#[test]
+fn test_source_terminator_is_kept_without_the_option() {
+    let path =
+        std::env::temp_dir().join(format!("line_terminator_keep_{}.csv", std::process::id()));
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "sample_id,status\r\nS1,active\r\nS2,Inactive\r\n").unwrap();
+
+    // An enum column alone rewrites the file, which must keep its CRLF line endings
+    let options = ExportOptions {
+        canonical_enums: HashMap::from([(
+            "status".to_string(),
+            vec!["Active".to_string(), "Inactive".to_string()],
+        )]),
+        ..Default::default()
+    };
+    options.apply(path).unwrap();
+
+    let content = std::fs::read(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(
+        content,
+        b"sample_id,status\r\nS1,Active\r\nS2,Inactive\r\n".to_vec()
+    );
+}
+
+#[test]
+fn test_line_terminator_parsing() {
+    assert_eq!("lf".parse::<LineTerminator>().unwrap(), LineTerminator::Lf);
+    assert_eq!(
+        "CRLF".parse::<LineTerminator>().unwrap(),
+        LineTerminator::Crlf
+    );
+    assert!("cr".parse::<LineTerminator>().is_err());
+}
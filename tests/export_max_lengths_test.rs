@@ -0,0 +1,83 @@
+//! Tests for the longest value per column report (--report-max-lengths)
+
+use dkan_importer::export::{find_max_lengths, ColumnMaxLength};
+use dkan_importer::model::{DataDictionary, ManifestEntry};
+use dkan_importer::summary::RunSummary;
+use importer_lib::serde_json::json;
+
+#[test]
+fn test_reports_max_length_and_first_row() {
+    let path = std::env::temp_dir().join(format!("max_lengths_{}.csv", std::process::id()));
+    std::fs::write(
+        &path,
+        "sample_id,station,notes,depth\nS1,Rovinj,short,10\nS2,Piran,a much longer note,1000\nS3,Koper,exactly eighteen!!,5\nS4,,,\n",
+    )
+    .unwrap();
+    let columns = vec![
+        "station".to_string(),
+        "notes".to_string(),
+        "empty".to_string(),
+    ];
+    let max_lengths = find_max_lengths(path.to_str().unwrap(), &columns).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        max_lengths,
+        vec![
+            ColumnMaxLength {
+                column: "station".to_string(),
+                length: 6,
+                row: 1,
+            },
+            // Ties keep the first row
+            ColumnMaxLength {
+                column: "notes".to_string(),
+                length: 18,
+                row: 2,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_lengths_count_characters() {
+    let path = std::env::temp_dir().join(format!("max_lengths_utf8_{}.csv", std::process::id()));
+    std::fs::write(&path, "station\nČakovec\nŠibenik–Knin\n").unwrap();
+    let max_lengths = find_max_lengths(path.to_str().unwrap(), &["station".to_string()]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(max_lengths[0].length, 12);
+    assert_eq!(max_lengths[0].row, 2);
+}
+
+#[test]
+fn test_string_properties_and_summary_json() {
+    let json_schema = json!({
+        "properties": {
+            "Station": {"type": "string"},
+            "Notes": {"type": ["string", "null"]},
+            "Depth": {"type": "integer"}
+        }
+    });
+    let mut strings = DataDictionary::string_properties(&json_schema);
+    strings.sort();
+    assert_eq!(strings, vec!["Notes", "Station"]);
+
+    let mut summary = RunSummary::new(&ManifestEntry {
+        excel_file: "./data/north_adriatic.xlsx".to_string(),
+        data_dictionary_id: "11111111-1111-1111-1111-111111111111".to_string(),
+        dataset_id: "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string(),
+        sheet_name: Some("Sample".to_string()),
+        distribution_filename: None,
+    });
+    assert!(summary.to_json()["max_lengths"].is_null());
+    summary.max_lengths = Some(vec![ColumnMaxLength {
+        column: "station".to_string(),
+        length: 6,
+        row: 1,
+    }]);
+    assert_eq!(
+        summary.to_json()["max_lengths"],
+        json!([{"column": "station", "length": 6, "row": 1}])
+    );
+}
@@ -0,0 +1,163 @@
+//! Tests for the cross-row checks of the exported CSV (--monotonic-increasing, --max-delta,
+//! --increasing-within-group)
+
+use dkan_importer::export::{
+    check_row_order, parse_grouped_column, parse_max_delta, RowOrderChecks, RowOrderViolation,
+};
+use std::collections::HashMap;
+
+fn write_csv(name: &str, content: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{name}_{}.csv", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+fn monotonic_index() -> RowOrderChecks {
+    RowOrderChecks {
+        monotonic_increasing: vec!["index".to_string()],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_non_monotonic_index_reports_the_row_pair() {
+    let path = write_csv(
+        "row_order_index",
+        "index,depth\n1,10\n2,11\n2,12\n,13\n5,14\n4,15\n",
+    );
+    let violations = check_row_order(&path, &monotonic_index()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        violations,
+        vec![
+            RowOrderViolation {
+                column: "index".to_string(),
+                previous_row: 2,
+                previous_value: "2".to_string(),
+                row: 3,
+                value: "2".to_string(),
+                reason: "does not increase".to_string(),
+            },
+            RowOrderViolation {
+                column: "index".to_string(),
+                previous_row: 5,
+                previous_value: "5".to_string(),
+                row: 6,
+                value: "4".to_string(),
+                reason: "does not increase".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_increasing_index_passes() {
+    // The empty value is skipped, row 4 is compared with row 2
+    let path = write_csv("row_order_increasing", "index\n1\n2.5\n\n3\n");
+    let violations = check_row_order(&path, &monotonic_index()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(violations, vec![]);
+}
+
+#[test]
+fn test_jump_beyond_max_delta() {
+    let path = write_csv("row_order_delta", "depth\n10\n14.5\n9.5\n9\n");
+    let checks = RowOrderChecks {
+        max_delta: HashMap::from([("depth".to_string(), 4.5)]),
+        ..Default::default()
+    };
+    let violations = check_row_order(&path, &checks).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // 10 → 14.5 is within the tolerance, 14.5 → 9.5 is not
+    assert_eq!(violations.len(), 1);
+    assert_eq!((violations[0].previous_row, violations[0].row), (2, 3));
+    assert_eq!(violations[0].reason, "differs by more than 4.5");
+}
+
+#[test]
+fn test_non_numeric_value_is_reported_once() {
+    let path = write_csv("row_order_text", "index\n1\nn/a\n2\n");
+    let violations = check_row_order(&path, &monotonic_index()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].row, 2);
+    assert_eq!(violations[0].reason, "is not a number");
+}
+
+#[test]
+fn test_unknown_column_is_an_error() {
+    let path = write_csv("row_order_unknown", "depth\n1\n");
+    let result = check_row_order(&path, &monotonic_index());
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_max_delta_parsing() {
+    assert_eq!(
+        parse_max_delta("Depth=5").unwrap(),
+        ("Depth".to_string(), 5.0)
+    );
+    assert!(parse_max_delta("Depth").is_err());
+    assert!(parse_max_delta("Depth=-1").is_err());
+    assert!(parse_max_delta("=1").is_err());
+}
+
+#[test]
+fn test_out_of_order_timestamp_within_a_station() {
+    let path = write_csv(
+        "row_order_grouped",
+        "station,timestamp\n\
+         S1,2024-05-01T10:00:00\n\
+         S2,2024-05-01T09:00:00\n\
+         S1,2024-05-01T11:00:00\n\
+         S2,2024-05-01T08:30:00\n\
+         S1,2024-05-01T12:00:00\n",
+    );
+    let checks = RowOrderChecks {
+        increasing_within_group: vec![("station".to_string(), "timestamp".to_string())],
+        ..Default::default()
+    };
+    let violations = check_row_order(&path, &checks).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Interleaved stations are fine, only S2 goes back in time
+    assert_eq!(
+        violations,
+        vec![RowOrderViolation {
+            column: "timestamp".to_string(),
+            previous_row: 2,
+            previous_value: "2024-05-01T09:00:00".to_string(),
+            row: 4,
+            value: "2024-05-01T08:30:00".to_string(),
+            reason: "does not increase within station 'S2'".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_grouped_numbers_are_compared_as_numbers() {
+    let path = write_csv("row_order_grouped_numbers", "station,index\nS1,9\nS1,10\n");
+    let checks = RowOrderChecks {
+        increasing_within_group: vec![("station".to_string(), "index".to_string())],
+        ..Default::default()
+    };
+    let violations = check_row_order(&path, &checks).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(violations, vec![]);
+}
+
+#[test]
+fn test_grouped_column_parsing() {
+    assert_eq!(
+        parse_grouped_column("Station:Timestamp").unwrap(),
+        ("Station".to_string(), "Timestamp".to_string())
+    );
+    assert!(parse_grouped_column("Timestamp").is_err());
+}
@@ -0,0 +1,249 @@
+//! Tests for checking the exported CSV against the schema before upload (--verify-export)
+
+use dkan_importer::export::{verify_export, ExportDiscrepancy, ExportOptions};
+use dkan_importer::model::DataDictionary;
+use importer_lib::serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn write_csv(name: &str, content: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{name}_{}.csv", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+/// Properties as generated from a data dictionary, keyed by CSV header name
+fn column_properties() -> HashMap<String, Value> {
+    HashMap::from([
+        (
+            "sample_id".to_string(),
+            json!({"type": "string", "minLength": 1, "pattern": "^S[0-9]+$"}),
+        ),
+        (
+            "temperature".to_string(),
+            json!({"type": ["number", "null"]}),
+        ),
+        ("depth".to_string(), json!({"type": "integer"})),
+        ("filtered".to_string(), json!({"type": ["boolean", "null"]})),
+        (
+            "method".to_string(),
+            json!({"type": ["string", "null"], "enum": ["CTD", "Niskin"]}),
+        ),
+    ])
+}
+
+#[test]
+fn test_faithful_export_passes() {
+    let path = write_csv(
+        "verify_ok",
+        "sample_id,temperature,depth,filtered,method,source_system\nS1,12.5,10,true,CTD,LabA\nS2,,20,,,LabA\n",
+    );
+    let discrepancies = verify_export(&path, &column_properties(), &[]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(discrepancies.is_empty(), "{discrepancies:?}");
+}
+
+#[test]
+fn test_lossy_export_is_caught() {
+    // A placeholder written for a missing number, a truncated id and a mangled enum value
+    let path = write_csv(
+        "verify_lossy",
+        "sample_id,temperature,depth,filtered,method\nS1,n/a,10,true,CTD\n,12.5,20.5,yes,Niskin bottle\n",
+    );
+    let discrepancies = verify_export(&path, &column_properties(), &[]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let found: Vec<(usize, &str, &str)> = discrepancies
+        .iter()
+        .map(|d| (d.row, d.column.as_str(), d.reason.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (1, "temperature", "expected number"),
+            (2, "sample_id", "required value is empty"),
+            (2, "depth", "expected integer"),
+            (2, "filtered", "expected boolean"),
+            (2, "method", "not one of the enum values"),
+        ]
+    );
+    assert_eq!(
+        discrepancies[0],
+        ExportDiscrepancy {
+            row: 1,
+            column: "temperature".to_string(),
+            value: "n/a".to_string(),
+            reason: "expected number".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_pattern_and_post_processing_options() {
+    let path = write_csv(
+        "verify_post_processing",
+        "sample_id,filtered,method\nX1,TRUE,ctd\n",
+    );
+    // Booleans written as 1/0 and canonical enum casing still verify
+    ExportOptions {
+        integer_booleans: vec!["filtered".to_string()],
+        canonical_enums: HashMap::from([(
+            "method".to_string(),
+            vec!["CTD".to_string(), "Niskin".to_string()],
+        )]),
+        ..Default::default()
+    }
+    .apply(&path)
+    .unwrap();
+    let discrepancies =
+        verify_export(&path, &column_properties(), &["filtered".to_string()]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(discrepancies.len(), 1);
+    assert_eq!(discrepancies[0].column, "sample_id");
+    assert_eq!(discrepancies[0].reason, "does not match pattern ^S[0-9]+$");
+}
+
+#[test]
+fn test_const_columns_are_checked() {
+    let path = write_csv("verify_const", "country,version\nHR,2\nhr,2\nSI,3\n");
+    let properties = HashMap::from([
+        (
+            "country".to_string(),
+            json!({"type": "string", "const": "HR"}),
+        ),
+        (
+            "version".to_string(),
+            json!({"type": "integer", "const": 2}),
+        ),
+    ]);
+    let discrepancies = verify_export(&path, &properties, &[]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let found: Vec<(usize, &str, &str)> = discrepancies
+        .iter()
+        .map(|d| (d.row, d.column.as_str(), d.reason.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (3, "country", "is not the constant \"HR\""),
+            (3, "version", "is not the constant 2"),
+        ]
+    );
+}
+
+#[test]
+fn test_integer_booleans_need_boolean_as_integer() {
+    let path = write_csv(
+        "verify_integer_booleans",
+        "sample_id,filtered\nS1,1\nS2,true\n",
+    );
+    // Without --boolean-as-integer 1/0 is a wrongly encoded boolean
+    let without_option = verify_export(&path, &column_properties(), &[]).unwrap();
+    // With it, true/false was left unconverted
+    let with_option =
+        verify_export(&path, &column_properties(), &["filtered".to_string()]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let found = |discrepancies: &[ExportDiscrepancy]| {
+        discrepancies
+            .iter()
+            .map(|d| (d.row, d.reason.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        found(&without_option),
+        vec![(1, "expected boolean".to_string())]
+    );
+    assert_eq!(
+        found(&with_option),
+        vec![(2, "expected 1 or 0".to_string())]
+    );
+}
+
+#[test]
+fn test_blank_required_string_is_caught_through_all_of() {
+    // The dictionary's pattern allows spaces, so the non-blank pattern sits next to it in allOf
+    let fields = DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Stations",
+        "fields": [{
+            "name": "station_name",
+            "title": "Station name",
+            "type": "string",
+            "constraints": {"required": true, "pattern": "^[A-Za-z ]*$"}
+        }]
+    }))
+    .unwrap();
+    let json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+    let property = json_schema["properties"]["Station name"].clone();
+    assert!(property.get("allOf").is_some(), "{property}");
+    let properties = HashMap::from([("station_name".to_string(), property)]);
+
+    let path = write_csv("verify_all_of", "station_name\nPula\n\"   \"\n");
+    let discrepancies = verify_export(&path, &properties, &[]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        discrepancies,
+        vec![ExportDiscrepancy {
+            row: 2,
+            column: "station_name".to_string(),
+            value: "   ".to_string(),
+            reason: r"does not match pattern \S".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_value_at_an_exclusive_bound_is_rejected() {
+    let fields = DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Samples",
+        "fields": [
+            {
+                "name": "depth",
+                "title": "Depth (m)",
+                "type": "number",
+                "constraints": {"exclusiveMinimum": 0}
+            },
+            {
+                "name": "replicate",
+                "title": "Replicate",
+                "type": "integer",
+                "constraints": {"exclusiveMaximum": 10}
+            }
+        ]
+    }))
+    .unwrap();
+    let json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+    let properties = HashMap::from([
+        (
+            "depth".to_string(),
+            json_schema["properties"]["Depth (m)"].clone(),
+        ),
+        (
+            "replicate".to_string(),
+            json_schema["properties"]["Replicate"].clone(),
+        ),
+    ]);
+
+    // Row 1 sits exactly on both bounds, row 2 just inside them
+    let path = write_csv(
+        "verify_exclusive_bounds",
+        "depth,replicate\n0,10\n0.001,9\n",
+    );
+    let discrepancies = verify_export(&path, &properties, &[]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let found: Vec<(usize, &str, &str)> = discrepancies
+        .iter()
+        .map(|d| (d.row, d.column.as_str(), d.reason.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (1, "depth", "is not above the exclusive minimum 0"),
+            (1, "replicate", "is not below the exclusive maximum 10"),
+        ]
+    );
+}
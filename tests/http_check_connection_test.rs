@@ -0,0 +1,54 @@
+//! Tests for the --check-connection health check against a mock DKAN server
+
+mod common;
+
+use common::{http_response, MockServer};
+use dkan_importer::utils::{build_client, check_connection, ConnectionStatus};
+use importer_lib::reqwest::StatusCode;
+use std::time::Duration;
+
+fn check_against(status: &str) -> (ConnectionStatus, String, Vec<String>) {
+    let server = MockServer::start(vec![http_response(status, &[], "[]")]);
+    let client = build_client(Duration::from_secs(5)).unwrap();
+    let connection_status = check_connection(&server.url, "user", "pass", &client);
+    let message = connection_status.message(&server.url);
+    (connection_status, message, server.requests())
+}
+
+#[test]
+fn test_reachable_instance_accepting_the_credentials() {
+    let (status, message, requests) = check_against("200 OK");
+
+    assert_eq!(status, ConnectionStatus::Connected);
+    assert!(message.starts_with("✅ Connected and authenticated to http://127.0.0.1:"));
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /api/1/metastore/schemas "));
+    // "user:pass" in base64
+    assert!(requests[0].contains("authorization: Basic dXNlcjpwYXNz"));
+}
+
+#[test]
+fn test_rejected_credentials_are_an_authentication_failure() {
+    for (status_line, status) in [
+        ("401 Unauthorized", StatusCode::UNAUTHORIZED),
+        ("403 Forbidden", StatusCode::FORBIDDEN),
+    ] {
+        let (connection_status, message, _) = check_against(status_line);
+
+        assert_eq!(
+            connection_status,
+            ConnectionStatus::AuthenticationFailed(status)
+        );
+        assert!(message.contains("Please check the username and password"));
+    }
+}
+
+#[test]
+fn test_missing_metastore_endpoint_is_an_http_error() {
+    // e.g. a --url pointing at a site that is not DKAN
+    let (status, message, _) = check_against("404 Not Found");
+
+    assert_eq!(status, ConnectionStatus::HttpError(StatusCode::NOT_FOUND));
+    assert!(message.ends_with("answered with an unexpected status: 404 Not Found"));
+    assert!(!status.is_connected());
+}
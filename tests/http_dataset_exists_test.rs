@@ -0,0 +1,63 @@
+//! Tests for the early dataset existence check (check_dataset_exists) against a mock DKAN server
+
+mod common;
+
+use common::{http_response, MockServer};
+use dkan_importer::utils::{build_client, check_dataset_exists};
+use std::time::Duration;
+
+const DATASET_ID: &str = "87654321-4321-8765-2109-876543210987";
+
+fn check_against(status: &str) -> (Result<(), importer_lib::anyhow::Error>, Vec<String>) {
+    let server = MockServer::start(vec![http_response(status, &[], "{}")]);
+    let client = build_client(Duration::from_secs(5)).unwrap();
+    let result = check_dataset_exists(&server.url, DATASET_ID, "user", "pass", &client);
+    (result, server.requests())
+}
+
+#[test]
+fn test_existing_dataset_passes() {
+    let (result, requests) = check_against("200 OK");
+
+    result.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with(&format!(
+        "GET /api/1/metastore/schemas/dataset/items/{DATASET_ID} "
+    )));
+    // "user:pass" in base64
+    assert!(requests[0].contains("authorization: Basic dXNlcjpwYXNz"));
+}
+
+#[test]
+fn test_missing_dataset_is_reported_as_not_found() {
+    let (result, _) = check_against("404 Not Found");
+
+    let error = result.unwrap_err().to_string();
+    assert!(error.starts_with(&format!(
+        "Dataset {DATASET_ID} not found. Please check the --dataset-id value"
+    )));
+}
+
+#[test]
+fn test_rejected_credentials_are_reported_as_such() {
+    for status in ["401 Unauthorized", "403 Forbidden"] {
+        let (result, _) = check_against(status);
+
+        let error = result.unwrap_err().to_string();
+        assert!(
+            error.starts_with(&format!("Not allowed to access dataset {DATASET_ID}")),
+            "{status}: {error}"
+        );
+        assert!(error.contains("Please check the username and password"));
+    }
+}
+
+#[test]
+fn test_server_error_names_the_status() {
+    let (result, _) = check_against("500 Internal Server Error");
+
+    let error = result.unwrap_err().to_string();
+    assert!(error.starts_with(&format!(
+        "Failed to check the existence of dataset {DATASET_ID} (500 Internal Server Error)"
+    )));
+}
@@ -0,0 +1,76 @@
+//! Tests for the request timeout of the DKAN HTTP client (--timeout-seconds)
+
+mod common;
+
+use common::{http_response, MockServer};
+use dkan_importer::model::DataDictionary;
+use dkan_importer::utils::{build_client, check_dataset_exists};
+use importer_lib::reqwest;
+use std::time::{Duration, Instant};
+
+fn is_timeout(error: &importer_lib::anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_timeout())
+    })
+}
+
+#[test]
+fn test_hung_server_times_out_instead_of_blocking() {
+    let server = MockServer::start_with_delay(
+        vec![http_response("200 OK", &[], "{}")],
+        Duration::from_secs(10),
+    );
+    let client = build_client(Duration::from_millis(300)).unwrap();
+
+    let start = Instant::now();
+    let error = check_dataset_exists(
+        &server.url,
+        "87654321-4321-8765-2109-876543210987",
+        "user",
+        "secret",
+        &client,
+    )
+    .unwrap_err();
+
+    assert!(is_timeout(&error), "{error:?}");
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_timeout_applies_to_the_data_dictionary_fetch() {
+    let server = MockServer::start_with_delay(
+        vec![http_response("200 OK", &[], "[]")],
+        Duration::from_secs(10),
+    );
+    let client = build_client(Duration::from_millis(300)).unwrap();
+
+    let start = Instant::now();
+    let Err(error) =
+        DataDictionary::new(&server.url, "12345678-1234-5678-9012-123456789012", &client)
+    else {
+        panic!("expected a timeout error");
+    };
+
+    assert!(is_timeout(&error), "{error:?}");
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_responses_within_the_timeout_are_unaffected() {
+    let server = MockServer::start_with_delay(
+        vec![http_response("200 OK", &[], "{}")],
+        Duration::from_millis(50),
+    );
+    let client = build_client(Duration::from_secs(5)).unwrap();
+
+    check_dataset_exists(
+        &server.url,
+        "87654321-4321-8765-2109-876543210987",
+        "user",
+        "secret",
+        &client,
+    )
+    .unwrap();
+}
@@ -0,0 +1,146 @@
+//! Tests for the manifest-driven batch mode
+
+use dkan_importer::model::{Manifest, ManifestEntry};
+use dkan_importer::utils::{check_spreadsheet_file, read_sheet_headers, resolve_sheet_name};
+use importer_lib::anyhow;
+use rust_xlsxwriter::Workbook;
+
+const TWO_ENTRY_MANIFEST: &str = r#"[
+    {
+        "excel_file": "./data/north_adriatic.xlsx",
+        "data_dictionary_id": "11111111-1111-1111-1111-111111111111",
+        "dataset_id": "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa",
+        "sheet_name": "Sample"
+    },
+    {
+        "excel_file": "./data/south_adriatic.xlsx",
+        "data_dictionary_id": "22222222-2222-2222-2222-222222222222",
+        "dataset_id": "bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb"
+    }
+]"#;
+
+#[test]
+fn test_manifest_parsing_with_default_sheet_name() {
+    let manifest = Manifest::from_json(TWO_ENTRY_MANIFEST).unwrap();
+
+    assert_eq!(manifest.entries.len(), 2);
+    assert_eq!(
+        manifest.entries[0],
+        ManifestEntry {
+            excel_file: "./data/north_adriatic.xlsx".to_string(),
+            data_dictionary_id: "11111111-1111-1111-1111-111111111111".to_string(),
+            dataset_id: "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string(),
+            sheet_name: Some("Sample".to_string()),
+            distribution_filename: None,
+        }
+    );
+    // Missing sheet_name selects the first sheet of the workbook
+    assert_eq!(manifest.entries[1].sheet_name, None);
+}
+
+#[test]
+fn test_entry_without_sheet_name_uses_the_first_sheet() {
+    let path =
+        std::env::temp_dir().join(format!("manifest_first_sheet_{}.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+    workbook.add_worksheet().set_name("Hoja1").unwrap();
+    workbook.add_worksheet().set_name("Hoja2").unwrap();
+    workbook.save(&path).unwrap();
+    let manifest = Manifest::from_json(&format!(
+        r#"[{{"excel_file": {:?}, "data_dictionary_id": "11111111-1111-1111-1111-111111111111", "dataset_id": "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa"}}]"#,
+        path.to_str().unwrap()
+    ))
+    .unwrap();
+
+    // Resolved the same way as for the command line when the entry is imported
+    let entry = &manifest.entries[0];
+    let sheet_name = resolve_sheet_name(&entry.excel_file, entry.sheet_name.as_deref(), None);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(sheet_name.unwrap(), "Hoja1");
+    assert!(entry.label().contains("(first sheet)"));
+}
+
+#[test]
+fn test_manifest_rejects_empty_and_malformed_input() {
+    assert!(Manifest::from_json("[]").is_err());
+    assert!(Manifest::from_json(r#"[{"excel_file": "a.xlsx"}]"#).is_err());
+    assert!(Manifest::from_json("not json").is_err());
+}
+
+#[test]
+fn test_batch_run_continues_after_failure_and_reports_both() {
+    let manifest = Manifest::from_json(TWO_ENTRY_MANIFEST).unwrap();
+
+    let mut processed = Vec::new();
+    let summary = manifest.run(|entry| {
+        processed.push(entry.excel_file.clone());
+        if entry.excel_file.contains("south") {
+            Err(anyhow::anyhow!("Validation failed with 3 errors"))
+        } else {
+            Ok(())
+        }
+    });
+
+    // Both entries ran, in order, even though the second one failed
+    assert_eq!(
+        processed,
+        vec!["./data/north_adriatic.xlsx", "./data/south_adriatic.xlsx"]
+    );
+    assert_eq!(summary.succeeded_count(), 1);
+    assert_eq!(summary.failed_count(), 1);
+    assert!(summary.has_failures());
+
+    let report = summary.report();
+    assert!(report.contains("1 of 2 entries succeeded"));
+    assert!(report.contains("✅ [1] ./data/north_adriatic.xlsx (Sample)"));
+    assert!(report.contains(
+        "❌ [2] ./data/south_adriatic.xlsx (first sheet) → dataset bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb: Validation failed with 3 errors"
+    ));
+}
+
+#[test]
+fn test_batch_run_reports_truncated_workbook_and_processes_the_next_one() {
+    let directory = std::env::temp_dir().join(format!("manifest_truncated_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let good = directory.join("good.xlsx");
+    let broken = directory.join("broken.xlsx");
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Sample ID").unwrap();
+    worksheet.write_string(1, 0, "S1").unwrap();
+    workbook.save(&good).unwrap();
+    // A copy cut short, as left by an interrupted transfer: still a ZIP signature, no directory
+    let content = std::fs::read(&good).unwrap();
+    std::fs::write(&broken, &content[..content.len() / 2]).unwrap();
+
+    let manifest = Manifest::from_json(&format!(
+        r#"[
+            {{"excel_file": {:?}, "data_dictionary_id": "11111111-1111-1111-1111-111111111111", "dataset_id": "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa"}},
+            {{"excel_file": {:?}, "data_dictionary_id": "11111111-1111-1111-1111-111111111111", "dataset_id": "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa"}}
+        ]"#,
+        broken.to_str().unwrap(),
+        good.to_str().unwrap()
+    ))
+    .unwrap();
+
+    // The first steps of an import: file check, sheet resolution and header row
+    let mut processed = Vec::new();
+    let summary = manifest.run(|entry| {
+        check_spreadsheet_file(&entry.excel_file)?;
+        let sheet_name = resolve_sheet_name(&entry.excel_file, entry.sheet_name.as_deref(), None)?;
+        processed.push(read_sheet_headers(&entry.excel_file, &sheet_name)?);
+        Ok(())
+    });
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    // The truncated workbook fails on its own, the next one is still read and the run is a
+    // partial failure
+    assert_eq!(processed, vec![vec!["Sample ID".to_string()]]);
+    assert_eq!(summary.succeeded_count(), 1);
+    assert_eq!(summary.failed_count(), 1);
+    assert!(summary.outcomes[0].error.is_some());
+    assert!(summary
+        .report()
+        .contains(&format!("❌ [1] {}", broken.to_str().unwrap())));
+}
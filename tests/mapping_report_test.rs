@@ -0,0 +1,109 @@
+//! Tests for the --mapping-report of sheet headers to data dictionary fields
+
+use dkan_importer::mapping::{
+    unmapped_headers_warning, write_mapping_report, HeaderMapping, MatchMethod,
+};
+use dkan_importer::model::DataDictionary;
+use dkan_importer::utils::read_raw_sheet_headers;
+use importer_lib::serde_json::{self, json, Value};
+use rust_xlsxwriter::Workbook;
+
+fn fields() -> Value {
+    DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Water samples",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID", "type": "string"},
+            {
+                "name": "temperature",
+                "title": "Temperature",
+                "type": "number",
+                "aliases": ["Temp"]
+            }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_report_lists_exact_alias_and_unmatched_headers() {
+    let path = std::env::temp_dir().join(format!("mapping_report_{}.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Sample ID").unwrap();
+    worksheet.write_string(0, 1, "Temp").unwrap();
+    worksheet.write_string(0, 2, "Field notes").unwrap();
+    workbook.save(&path).unwrap();
+    let headers = read_raw_sheet_headers(path.to_str().unwrap(), "Sheet1");
+    std::fs::remove_file(&path).unwrap();
+
+    let fields = fields();
+    let mut json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+    let mut mapping = DataDictionary::create_title_to_name_mapping(&fields).unwrap();
+    DataDictionary::add_field_aliases(&mut json_schema, &fields, &mut mapping).unwrap();
+    let mappings = HeaderMapping::from_headers(&headers.unwrap(), &fields, &mapping).unwrap();
+
+    assert_eq!(mappings.len(), 3);
+    assert_eq!(mappings[0].field.as_deref(), Some("sample_id"));
+    assert_eq!(mappings[0].method, Some(MatchMethod::Exact));
+    assert_eq!(mappings[1].field.as_deref(), Some("temperature"));
+    assert_eq!(mappings[1].method, Some(MatchMethod::Alias));
+    assert_eq!(mappings[2].field, None);
+    assert_eq!(mappings[2].method, None);
+}
+
+#[test]
+fn test_report_is_written_as_json() {
+    let fields = fields();
+    let mapping = DataDictionary::create_title_to_name_mapping(&fields).unwrap();
+    let headers = vec![
+        "Sample  ID".to_string(),
+        "Comments".to_string(),
+        String::new(),
+    ];
+    let mappings = HeaderMapping::from_headers(&headers, &fields, &mapping).unwrap();
+
+    let path = std::env::temp_dir().join(format!("mapping_report_{}.json", std::process::id()));
+    write_mapping_report(&mappings, path.to_str().unwrap()).unwrap();
+    let report: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Blank header cells are left out
+    assert_eq!(
+        report,
+        json!([
+            {"header": "Sample  ID", "normalized": "Sample ID", "field": "sample_id", "method": "normalized"},
+            {"header": "Comments", "normalized": "Comments", "field": null, "method": null}
+        ])
+    );
+}
+
+#[test]
+fn test_case_insensitive_match_is_reported() {
+    let fields = fields();
+    let mut json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+    let mut mapping = DataDictionary::create_title_to_name_mapping(&fields).unwrap();
+    let headers = vec!["sample id".to_string()];
+    DataDictionary::add_case_insensitive_aliases(&mut json_schema, &mut mapping, &headers).unwrap();
+    let mappings = HeaderMapping::from_headers(&headers, &fields, &mapping).unwrap();
+
+    assert_eq!(mappings[0].field.as_deref(), Some("sample_id"));
+    assert_eq!(mappings[0].method, Some(MatchMethod::CaseInsensitive));
+}
+
+#[test]
+fn test_unmapped_column_is_warned_about() {
+    let fields = fields();
+    let mapping = DataDictionary::create_title_to_name_mapping(&fields).unwrap();
+    let headers = vec![
+        "Sample ID".to_string(),
+        "Temprature".to_string(),
+        "Field notes".to_string(),
+    ];
+    let mappings = HeaderMapping::from_headers(&headers, &fields, &mapping).unwrap();
+
+    assert_eq!(
+        unmapped_headers_warning(&mappings).as_deref(),
+        Some("⚠️ 2 columns match no data dictionary field and are converted without a field schema: Temprature, Field notes")
+    );
+    assert_eq!(unmapped_headers_warning(&mappings[..1]), None);
+}
@@ -0,0 +1,140 @@
+//! Tests for routing user-facing messages through `Output` and the --quiet flag
+
+use dkan_importer::output::{Output, Verbosity};
+use importer_lib::ERRORS_LOG_FILE;
+use std::io::Write;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// Writer that keeps everything written to it, shared with the test
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn output_with(verbosity: Verbosity) -> (Output, SharedBuffer, SharedBuffer) {
+    let out = SharedBuffer::default();
+    let err = SharedBuffer::default();
+    let output = Output::new(verbosity, Box::new(out.clone()), Box::new(err.clone()));
+    (output, out, err)
+}
+
+fn write_all_levels(output: &Output) {
+    output.info("✅ Validation completed!");
+    output.detail("📖 Data dictionary fetched");
+    output.warn("⚠️ Failed to cache the validation result");
+    output.error("❌ Validation failed with 2 errors");
+}
+
+#[test]
+fn test_quiet_only_writes_errors() {
+    let (output, out, err) = output_with(Verbosity::Quiet);
+    write_all_levels(&output);
+
+    assert_eq!(out.contents(), "");
+    assert_eq!(err.contents(), "❌ Validation failed with 2 errors\n");
+}
+
+#[test]
+fn test_normal_skips_details() {
+    let (output, out, err) = output_with(Verbosity::Normal);
+    write_all_levels(&output);
+
+    assert_eq!(out.contents(), "✅ Validation completed!\n");
+    assert_eq!(
+        err.contents(),
+        "⚠️ Failed to cache the validation result\n❌ Validation failed with 2 errors\n"
+    );
+}
+
+#[test]
+fn test_verbose_writes_everything() {
+    let (output, out, _err) = output_with(Verbosity::Verbose);
+    write_all_levels(&output);
+
+    assert_eq!(
+        out.contents(),
+        "✅ Validation completed!\n📖 Data dictionary fetched\n"
+    );
+}
+
+#[test]
+fn test_results_are_written_even_when_quiet() {
+    let (output, out, _err) = output_with(Verbosity::Quiet);
+    output.result("{\"distribution_url\":\"https://dkan.example.com/files/a.csv\"}");
+
+    assert_eq!(
+        out.contents(),
+        "{\"distribution_url\":\"https://dkan.example.com/files/a.csv\"}\n"
+    );
+}
+
+#[test]
+fn test_quiet_validation_failure_still_points_to_errors_log() {
+    let (output, out, err) = output_with(Verbosity::Quiet);
+    output.info("📖 Validating sheet 'Samples'");
+    output.validation_failed(3, ERRORS_LOG_FILE);
+
+    assert_eq!(out.contents(), "");
+    assert_eq!(
+        err.contents(),
+        "❌ Validation failed with 3 errors\n❌ Check errors.log for details.\n"
+    );
+}
+
+#[test]
+fn test_quiet_failure_keeps_stdout_empty_and_exit_code() {
+    let result = Command::new(env!("CARGO_BIN_EXE_dkan-importer"))
+        .args([
+            "--base-url",
+            "https://127.0.0.1:1",
+            "--username",
+            "user",
+            "--password",
+            "secret",
+            "--check-connection",
+            "--quiet",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!result.status.success());
+    assert!(result.stdout.is_empty());
+    assert!(!result.stderr.is_empty());
+}
+
+#[test]
+fn test_quiet_and_verbose_conflict() {
+    let result = Command::new(env!("CARGO_BIN_EXE_dkan-importer"))
+        .args([
+            "--base-url",
+            "https://127.0.0.1:1",
+            "--username",
+            "user",
+            "--password",
+            "secret",
+            "--check-connection",
+            "--quiet",
+            "--verbose",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!result.status.success());
+    assert!(result.stdout.is_empty());
+}
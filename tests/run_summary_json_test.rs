@@ -0,0 +1,81 @@
+//! Tests for the --summary-json run summary
+
+use dkan_importer::model::ManifestEntry;
+use dkan_importer::summary::RunSummary;
+use importer_lib::serde_json::{self, json, Value};
+use std::time::Duration;
+
+fn entry() -> ManifestEntry {
+    ManifestEntry {
+        excel_file: "./data/north_adriatic.xlsx".to_string(),
+        data_dictionary_id: "11111111-1111-1111-1111-111111111111".to_string(),
+        dataset_id: "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string(),
+        sheet_name: Some("Sample".to_string()),
+        distribution_filename: None,
+    }
+}
+
+#[test]
+fn test_successful_run_summary_shape() {
+    let mut summary = RunSummary::new(&entry());
+    summary.timings.validation = Some(Duration::from_millis(1500));
+    summary.timings.export = Some(Duration::from_millis(250));
+    summary.timings.upload = Some(Duration::from_secs(2));
+    summary.timings.row_count = 120;
+    summary.timings.uploaded_bytes = 4096;
+    summary.total_rows = Some(120);
+    summary.invalid_rows = Some(0);
+
+    let path = std::env::temp_dir().join(format!("run_summary_{}.json", std::process::id()));
+    summary.write(path.to_str().unwrap()).unwrap();
+    let written: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(written["excel_file"], "./data/north_adriatic.xlsx");
+    assert_eq!(written["sheet_name"], "Sample");
+    assert_eq!(written["succeeded"], true);
+    assert_eq!(written["error"], Value::Null);
+    assert_eq!(written["error_count"], 0);
+    assert_eq!(written["total_rows"], 120);
+    assert_eq!(written["valid_rows"], 120);
+    assert_eq!(written["invalid_rows"], 0);
+    assert_eq!(written["exported_rows"], 120);
+    assert_eq!(written["uploaded_bytes"], 4096);
+    assert_eq!(
+        written["timings"],
+        json!({"validation_seconds": 1.5, "export_seconds": 0.25, "upload_seconds": 2.0})
+    );
+    assert!(written["created_at"].is_string());
+}
+
+#[test]
+fn test_failed_validation_summary_has_no_exported_rows() {
+    let mut summary = RunSummary::new(&entry());
+    summary.timings.validation = Some(Duration::from_secs(1));
+    summary.error_count = 7;
+    summary.error =
+        Some("Validation of ./data/north_adriatic.xlsx failed with 7 errors".to_string());
+
+    let json = summary.to_json();
+    assert_eq!(json["succeeded"], false);
+    assert_eq!(json["error_count"], 7);
+    assert_eq!(json["exported_rows"], Value::Null);
+    assert_eq!(json["timings"]["export_seconds"], Value::Null);
+    // Row counts that were not filled in
+    assert_eq!(json["total_rows"], Value::Null);
+    assert_eq!(json["valid_rows"], Value::Null);
+    assert_eq!(json["invalid_rows"], Value::Null);
+}
+
+#[test]
+fn test_failed_validation_summary_counts_valid_and_invalid_rows() {
+    let mut summary = RunSummary::new(&entry());
+    summary.error_count = 3;
+    summary.total_rows = Some(10);
+    summary.invalid_rows = Some(3);
+
+    let json = summary.to_json();
+    assert_eq!(json["total_rows"], 10);
+    assert_eq!(json["valid_rows"], 7);
+    assert_eq!(json["invalid_rows"], 3);
+}
@@ -0,0 +1,110 @@
+//! Tests for reporting blank cells of required columns before validation
+
+use dkan_importer::model::DataDictionary;
+use dkan_importer::sheet::{BlankCell, SheetTable};
+use dkan_importer::utils::read_sheet_headers;
+use importer_lib::serde_json::json;
+use rust_xlsxwriter::Workbook;
+
+/// Sample ID* is required, Notes is optional. Row 4 (the third sample) has a Sample ID made of
+/// spaces only and row 5 has none.
+fn write_workbook(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{name}_{}.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Sample ID *").unwrap();
+    worksheet.write_string(0, 1, "Depth *").unwrap();
+    worksheet.write_string(0, 2, "Notes").unwrap();
+    worksheet.write_string(1, 0, "S1").unwrap();
+    worksheet.write_number(1, 1, 10).unwrap();
+    worksheet.write_string(2, 0, "S2").unwrap();
+    worksheet.write_number(2, 1, 20).unwrap();
+    worksheet.write_string(3, 0, "   ").unwrap();
+    worksheet.write_number(3, 1, 30).unwrap();
+    worksheet.write_string(4, 2, "depth not measured").unwrap();
+    workbook.save(&path).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+fn required_headers(path: &str) -> Vec<String> {
+    let fields = DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Samples",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID *", "type": "string"},
+            {"name": "depth", "title": "Depth *", "type": "number"},
+            {"name": "notes", "title": "Notes", "type": "string"}
+        ]
+    }))
+    .unwrap();
+    let json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+    let headers = read_sheet_headers(path, "Sheet1").unwrap();
+    DataDictionary::required_headers(&json_schema, &headers)
+}
+
+#[test]
+fn test_required_cell_with_only_spaces_is_blank() {
+    let path = write_workbook("blank_required");
+    let required = required_headers(&path);
+    let blank_cells = SheetTable::read(&path, "Sheet1", &required)
+        .unwrap()
+        .blank_cells(&required);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(required, vec!["Sample ID*", "Depth*"]);
+    // The optional Notes column may be blank
+    assert_eq!(
+        blank_cells,
+        vec![
+            BlankCell {
+                row: 4,
+                column: "Sample ID*".to_string()
+            },
+            BlankCell {
+                row: 5,
+                column: "Sample ID*".to_string()
+            },
+            BlankCell {
+                row: 5,
+                column: "Depth*".to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_required_field_accepted_under_an_alias_is_checked_under_that_header() {
+    let fields = DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Samples",
+        "fields": [{"name": "sample_id", "title": "Sample ID *", "type": "string", "aliases": ["ID"]}]
+    }))
+    .unwrap();
+    let mut json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields).unwrap();
+    let mut mapping = DataDictionary::create_title_to_name_mapping(&fields).unwrap();
+    DataDictionary::add_field_aliases(&mut json_schema, &fields, &mut mapping).unwrap();
+
+    let headers = vec!["ID".to_string(), "Notes".to_string()];
+    assert_eq!(
+        DataDictionary::required_headers(&json_schema, &headers),
+        vec!["ID"]
+    );
+}
+
+#[test]
+fn test_only_checked_columns_are_read() {
+    let path = write_workbook("blank_required_columns");
+    let table = SheetTable::read(&path, "Sheet1", &["Depth*".to_string()]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Row 5 only has a note, yet it is a data row with a blank depth
+    assert_eq!(
+        table.rows,
+        vec![
+            (2, vec!["10".to_string()]),
+            (3, vec!["20".to_string()]),
+            (4, vec!["30".to_string()]),
+            (5, vec![String::new()]),
+        ]
+    );
+    assert_eq!(table.column_index("Depth*"), Some(0));
+    assert_eq!(table.column_index("Notes"), None);
+}
@@ -0,0 +1,53 @@
+//! Tests for reporting cells that hold a formula instead of its value
+
+use dkan_importer::sheet::{FormulaCell, SheetTable};
+use rust_xlsxwriter::{Formula, Workbook};
+
+/// Depth is numeric. Row 3 holds the text of a formula, row 4 a real formula whose cached value
+/// calamine returns.
+fn write_workbook(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{name}_{}.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Sample ID").unwrap();
+    worksheet.write_string(0, 1, "Depth").unwrap();
+    worksheet.write_string(1, 0, "S1").unwrap();
+    worksheet.write_number(1, 1, 10).unwrap();
+    worksheet.write_string(2, 0, "S2").unwrap();
+    worksheet.write_string(2, 1, "=B2+5").unwrap();
+    worksheet.write_string(3, 0, "S3").unwrap();
+    worksheet
+        .write_formula(3, 1, Formula::new("=B2*2").set_result("20"))
+        .unwrap();
+    workbook.save(&path).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_formula_text_in_numeric_column_is_reported() {
+    let path = write_workbook("formula_cells");
+    let columns = vec!["Sample ID".to_string(), "Depth".to_string()];
+    let formula_cells = SheetTable::read(&path, "Sheet1", &columns)
+        .unwrap()
+        .formula_cells(&columns);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        formula_cells,
+        vec![FormulaCell {
+            row: 3,
+            column: "Depth".to_string(),
+            formula: "=B2+5".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_unread_columns_are_not_checked() {
+    let path = write_workbook("formula_cells_unread");
+    let columns = vec!["Sample ID".to_string()];
+    let table = SheetTable::read(&path, "Sheet1", &columns).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(table.formula_cells(&["Depth".to_string()]).is_empty());
+}
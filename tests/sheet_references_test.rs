@@ -0,0 +1,84 @@
+//! Tests for cross-sheet reference checks (--reference)
+
+use dkan_importer::sheet::{BrokenReference, SheetReference};
+use rust_xlsxwriter::Workbook;
+use std::str::FromStr;
+
+/// Stations sheet with keys S1 and S2, and a Samples sheet referencing them
+fn workbook_with_samples(name: &str, stations: &[&str]) -> String {
+    let path = std::env::temp_dir().join(format!("{name}_{}.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+    let parent = workbook.add_worksheet().set_name("Stations").unwrap();
+    parent.write_string(0, 0, "ID").unwrap();
+    parent.write_string(0, 1, "Name").unwrap();
+    parent.write_string(1, 0, "S1").unwrap();
+    parent.write_string(2, 0, "S2").unwrap();
+    parent.write_number(3, 0, 7).unwrap();
+    let child = workbook.add_worksheet().set_name("Samples").unwrap();
+    child.write_string(0, 0, "Sample ID").unwrap();
+    child.write_string(0, 1, "Station ID").unwrap();
+    for (index, station) in stations.iter().enumerate() {
+        let row = index as u32 + 1;
+        child.write_string(row, 0, format!("SAMPLE-{row}")).unwrap();
+        child.write_string(row, 1, *station).unwrap();
+    }
+    workbook.save(&path).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+fn reference() -> SheetReference {
+    SheetReference::from_str("Samples:Station ID=Stations:ID").unwrap()
+}
+
+#[test]
+fn test_reference_to_a_missing_parent_key_is_reported() {
+    let path = workbook_with_samples("reference_missing_key", &["S1", "S3", "S2"]);
+    let broken = reference().check(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    // Row 3 of the sheet, below the header and the first sample
+    assert_eq!(
+        broken.unwrap(),
+        vec![BrokenReference {
+            row: 3,
+            value: "S3".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_valid_references_and_blank_values_pass() {
+    let path = workbook_with_samples("reference_valid", &["S1", " S2 ", "", "7"]);
+    let broken = reference().check(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(broken.unwrap(), vec![]);
+}
+
+#[test]
+fn test_unknown_column_is_an_error() {
+    let path = workbook_with_samples("reference_unknown_column", &["S1"]);
+    let reference = SheetReference::from_str("Samples:Station=Stations:ID").unwrap();
+    let error = reference.check(&path).unwrap_err().to_string();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        error,
+        "Reference Samples:Station=Stations:ID: sheet 'Samples': Column 'Station' not found, the sheet's headers are: Sample ID, Station ID"
+    );
+}
+
+#[test]
+fn test_reference_parsing() {
+    assert_eq!(
+        SheetReference::from_str("Samples : Station ID = Stations:ID").unwrap(),
+        SheetReference {
+            child_sheet: "Samples".to_string(),
+            child_column: "Station ID".to_string(),
+            parent_sheet: "Stations".to_string(),
+            parent_column: "ID".to_string(),
+        }
+    );
+    assert!(SheetReference::from_str("Samples:Station ID").is_err());
+    assert!(SheetReference::from_str("Station ID=Stations:ID").is_err());
+}
@@ -0,0 +1,77 @@
+//! Tests for reporting required columns missing from the sheet header once, before row validation
+
+use dkan_importer::model::DataDictionary;
+use dkan_importer::utils::read_sheet_headers;
+use importer_lib::serde_json::{json, Value};
+use rust_xlsxwriter::Workbook;
+use std::collections::HashMap;
+
+fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "Sample ID*": {"type": "string"},
+            "Station*": {"type": "string"},
+            "Temperature": {"type": ["number", "null"]}
+        },
+        "required": ["Sample ID*", "Station*"]
+    })
+}
+
+fn headers(columns: &[&str]) -> Vec<String> {
+    columns.iter().map(|c| c.to_string()).collect()
+}
+
+#[test]
+fn test_missing_required_column_is_reported_once() {
+    let missing = DataDictionary::missing_required_columns(
+        &schema(),
+        &headers(&["Sample ID*", "Temperature"]),
+    );
+
+    assert_eq!(missing, vec!["'Station*'"]);
+}
+
+#[test]
+fn test_all_required_columns_present() {
+    let missing = DataDictionary::missing_required_columns(
+        &schema(),
+        &headers(&["Temperature", "Station*", "Sample ID*"]),
+    );
+
+    assert!(missing.is_empty());
+}
+
+#[test]
+fn test_machine_name_counts_as_the_required_column() {
+    let mut json_schema = schema();
+    let mut mapping = HashMap::from([
+        ("Sample ID*".to_string(), "sample_id".to_string()),
+        ("Station*".to_string(), "station".to_string()),
+        ("Temperature".to_string(), "temperature".to_string()),
+    ]);
+    DataDictionary::add_machine_name_aliases(&mut json_schema, &mut mapping).unwrap();
+
+    let missing = DataDictionary::missing_required_columns(&json_schema, &headers(&["sample_id"]));
+
+    assert_eq!(missing, vec!["'Station*' (or 'station')"]);
+}
+
+#[test]
+fn test_read_sheet_headers_reads_the_first_row() {
+    let path = std::env::temp_dir().join(format!("sheet_headers_{}.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet().set_name("Sample").unwrap();
+    worksheet.write_string(0, 0, "Sample ID*").unwrap();
+    worksheet.write_string(0, 1, " Temperature ").unwrap();
+    worksheet.write_string(1, 0, "S1").unwrap();
+    worksheet.write_number(1, 1, 12.5).unwrap();
+    workbook.save(&path).unwrap();
+
+    let headers = read_sheet_headers(path.to_str().unwrap(), "Sample").unwrap();
+    let error = read_sheet_headers(path.to_str().unwrap(), "Sheet9").unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(headers, vec!["Sample ID*", "Temperature"]);
+    assert!(error.to_string().contains("sheet 'Sheet9'"));
+}
@@ -0,0 +1,52 @@
+//! Tests for selecting the sheet by name, by index or by default (--sheet-name, --sheet-index)
+
+use dkan_importer::utils::resolve_sheet_name;
+use rust_xlsxwriter::Workbook;
+
+/// Workbook with localized sheet names, as saved by a Spanish Excel
+fn localized_workbook(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{name}_{}.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+    workbook.add_worksheet().set_name("Hoja1").unwrap();
+    workbook.add_worksheet().set_name("Hoja2").unwrap();
+    workbook.save(&path).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_default_is_the_first_sheet() {
+    let path = localized_workbook("sheet_default");
+    let sheet_name = resolve_sheet_name(&path, None, None).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(sheet_name, "Hoja1");
+}
+
+#[test]
+fn test_sheet_by_index() {
+    let path = localized_workbook("sheet_index");
+    let sheet_name = resolve_sheet_name(&path, None, Some(1)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(sheet_name, "Hoja2");
+}
+
+#[test]
+fn test_sheet_index_out_of_range() {
+    let path = localized_workbook("sheet_out_of_range");
+    let error = resolve_sheet_name(&path, None, Some(2))
+        .unwrap_err()
+        .to_string();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(error.starts_with("Sheet index 2 is out of range"));
+    assert!(error.contains("has 2 sheets (Hoja1, Hoja2)"));
+}
+
+#[test]
+fn test_sheet_name_is_used_as_given() {
+    // The workbook isn't opened when the name is given
+    let sheet_name = resolve_sheet_name("./does-not-exist.xlsx", Some("Sample"), None).unwrap();
+
+    assert_eq!(sheet_name, "Sample");
+}
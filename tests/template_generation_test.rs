@@ -0,0 +1,96 @@
+//! Tests for the blank template spreadsheet (--generate-template)
+
+use calamine::{open_workbook, Data, Reader, Xlsx};
+use dkan_importer::model::DataDictionary;
+use dkan_importer::template::{write_template, TemplateColumn, TEMPLATE_SHEET_NAME};
+use importer_lib::serde_json::json;
+
+fn fields() -> importer_lib::serde_json::Value {
+    DataDictionary::normalize_field_data_for_tests(json!({
+        "title": "Sampling",
+        "fields": [
+            {"name": "sample_id", "title": "Sample ID*", "type": "string", "description": "Laboratory sample code"},
+            {"name": "temperature", "title": "Temperature (°C)", "type": "number"},
+            {"name": "depth", "type": "integer", "description": ""}
+        ]
+    }))
+    .unwrap()
+}
+
+fn read_rows(path: &str) -> Vec<Vec<String>> {
+    let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
+    let range = workbook.worksheet_range(TEMPLATE_SHEET_NAME).unwrap();
+    range
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Data::String(s) => s.clone(),
+                    Data::Empty => String::new(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_template_columns_follow_the_dictionary() {
+    let columns = TemplateColumn::from_fields(&fields()).unwrap();
+
+    assert_eq!(
+        columns,
+        vec![
+            TemplateColumn {
+                header: "Sample ID*".to_string(),
+                description: Some("Laboratory sample code".to_string()),
+            },
+            TemplateColumn {
+                header: "Temperature (°C)".to_string(),
+                description: None,
+            },
+            // Fields without a title use their name, as in the generated schema
+            TemplateColumn {
+                header: "depth".to_string(),
+                description: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_template_header_row_matches_titles_and_required_markers() {
+    let path = std::env::temp_dir().join(format!("template_{}.xlsx", std::process::id()));
+    let path = path.to_str().unwrap();
+    let columns = TemplateColumn::from_fields(&fields()).unwrap();
+    write_template(path, &columns, false).unwrap();
+    let rows = read_rows(path);
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(rows, vec![vec!["Sample ID*", "Temperature (°C)", "depth"]]);
+
+    // The header row is exactly the set of schema properties the validator expects
+    let json_schema = DataDictionary::convert_data_dictionary_to_json_schema(&fields()).unwrap();
+    let mut properties: Vec<&String> = json_schema["properties"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .collect();
+    properties.sort();
+    let mut headers = rows[0].iter().collect::<Vec<_>>();
+    headers.sort();
+    assert_eq!(headers, properties);
+}
+
+#[test]
+fn test_template_with_descriptions_row() {
+    let path = std::env::temp_dir().join(format!("template_desc_{}.xlsx", std::process::id()));
+    let path = path.to_str().unwrap();
+    let columns = TemplateColumn::from_fields(&fields()).unwrap();
+    write_template(path, &columns, true).unwrap();
+    let rows = read_rows(path);
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1], vec!["Laboratory sample code", "", ""]);
+}
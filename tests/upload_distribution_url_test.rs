@@ -0,0 +1,70 @@
+//! Tests for reporting the DKAN URLs of an uploaded distribution
+
+mod common;
+
+use common::{http_response, MockServer};
+use dkan_importer::output::{Output, Verbosity};
+use dkan_importer::utils::{upload_distribution_csv_file, RetryPolicy, UploadedDistribution};
+use importer_lib::reqwest::blocking::Client;
+use importer_lib::serde_json::{self, json};
+
+#[test]
+fn test_reported_url_is_the_one_returned_by_dkan() {
+    let path = std::env::temp_dir().join(format!("distribution_url_{}.csv", std::process::id()));
+    std::fs::write(&path, "sample_id\nS1\n").unwrap();
+    let server = MockServer::start(vec![http_response(
+        "200 OK",
+        &[],
+        r#"{"data": {"file_url": "https://dkan.example.com/sites/default/files/uploaded_resources/samples.csv"}}"#,
+    )]);
+
+    let file_url = upload_distribution_csv_file(
+        &server.url,
+        path.to_str().unwrap(),
+        "samples.csv",
+        "user",
+        "secret",
+        &Client::new(),
+        &RetryPolicy::default(),
+        &Output::stdio(Verbosity::Quiet),
+    )
+    .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let uploaded = UploadedDistribution::new(
+        "https://dkan.example.com",
+        "87654321-4321-8765-2109-876543210987",
+        "samples.csv",
+        &file_url,
+    );
+    assert_eq!(
+        uploaded.distribution_url,
+        "https://dkan.example.com/sites/default/files/uploaded_resources/samples.csv"
+    );
+    assert_eq!(
+        uploaded.dataset_url,
+        "https://dkan.example.com/dataset/87654321-4321-8765-2109-876543210987"
+    );
+}
+
+#[test]
+fn test_json_result_is_a_single_line() {
+    let uploaded = UploadedDistribution::new(
+        "https://dkan.example.com",
+        "dataset-1",
+        "samples.csv",
+        "https://dkan.example.com/files/samples.csv",
+    );
+
+    let line = uploaded.to_json();
+    assert!(!line.contains('\n'));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&line).unwrap(),
+        json!({
+            "dataset_id": "dataset-1",
+            "dataset_url": "https://dkan.example.com/dataset/dataset-1",
+            "distribution_filename": "samples.csv",
+            "distribution_url": "https://dkan.example.com/files/samples.csv"
+        })
+    );
+}
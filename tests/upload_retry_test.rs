@@ -0,0 +1,185 @@
+//! Tests for retrying DKAN requests with exponential backoff (--max-retries)
+
+mod common;
+
+use common::{http_response, MockServer};
+use dkan_importer::output::{Output, Verbosity};
+use dkan_importer::utils::{
+    build_client, delete_remote_file, upload_distribution_csv_file, RetryPolicy,
+};
+use importer_lib::reqwest::blocking::Client;
+use std::time::Duration;
+
+fn fast_retries(max_retries: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_retries,
+        base_delay: Duration::from_millis(1),
+    }
+}
+
+fn quiet() -> Output {
+    Output::stdio(Verbosity::Quiet)
+}
+
+#[test]
+fn test_upload_succeeds_after_two_503() {
+    let path = std::env::temp_dir().join(format!("retry_upload_{}.csv", std::process::id()));
+    std::fs::write(&path, "sample_id\nS1\n").unwrap();
+    let server = MockServer::start(vec![
+        http_response("503 Service Unavailable", &[], "busy"),
+        http_response("503 Service Unavailable", &[], "busy"),
+        http_response(
+            "200 OK",
+            &[],
+            r#"{"data": {"file_url": "https://dkan.example.com/files/samples.csv"}}"#,
+        ),
+    ]);
+
+    let file_url = upload_distribution_csv_file(
+        &server.url,
+        path.to_str().unwrap(),
+        "samples.csv",
+        "user",
+        "secret",
+        &Client::new(),
+        &fast_retries(3),
+        &quiet(),
+    )
+    .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(file_url, "https://dkan.example.com/files/samples.csv");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    // The file is sent again in full on every attempt
+    assert!(requests
+        .iter()
+        .all(|request| request.contains("sample_id\nS1\n")));
+}
+
+#[test]
+fn test_timed_out_upload_is_not_sent_again() {
+    let path = std::env::temp_dir().join(format!("timeout_upload_{}.csv", std::process::id()));
+    std::fs::write(&path, "sample_id\nS1\n").unwrap();
+    let uploaded = r#"{"data": {"file_url": "https://dkan.example.com/files/samples.csv"}}"#;
+    // DKAN receives the upload but answers after the client gave up
+    let server = MockServer::start_with_delay(
+        vec![
+            http_response("200 OK", &[], uploaded),
+            http_response("200 OK", &[], uploaded),
+        ],
+        Duration::from_millis(800),
+    );
+
+    let result = upload_distribution_csv_file(
+        &server.url,
+        path.to_str().unwrap(),
+        "samples.csv",
+        "user",
+        "secret",
+        &build_client(Duration::from_millis(200)).unwrap(),
+        &fast_retries(3),
+        &quiet(),
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err());
+    // A resent upload would be received once the server is done with the first one
+    std::thread::sleep(Duration::from_millis(1500));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_upload_answered_with_500_is_not_sent_again() {
+    let path = std::env::temp_dir().join(format!("error_upload_{}.csv", std::process::id()));
+    std::fs::write(&path, "sample_id\nS1\n").unwrap();
+    // DKAN may have stored the file before failing
+    let server = MockServer::start(vec![
+        http_response("500 Internal Server Error", &[], "server error"),
+        http_response(
+            "200 OK",
+            &[],
+            r#"{"data": {"file_url": "https://dkan.example.com/files/samples.csv"}}"#,
+        ),
+    ]);
+
+    let error = upload_distribution_csv_file(
+        &server.url,
+        path.to_str().unwrap(),
+        "samples.csv",
+        "user",
+        "secret",
+        &Client::new(),
+        &fast_retries(3),
+        &quiet(),
+    )
+    .unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(
+        error.to_string(),
+        "Custom importer upload failed: server error"
+    );
+}
+
+#[test]
+fn test_client_errors_are_not_retried() {
+    let server = MockServer::start(vec![
+        http_response("404 Not Found", &[], "no such file"),
+        http_response("200 OK", &[], ""),
+    ]);
+
+    let error = delete_remote_file(
+        &server.url,
+        "old.csv",
+        "user",
+        "secret",
+        &Client::new(),
+        &fast_retries(3),
+        &quiet(),
+    )
+    .unwrap_err();
+
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(
+        error.to_string(),
+        "Failed to delete file old.csv: no such file"
+    );
+}
+
+#[test]
+fn test_gives_up_after_max_retries() {
+    let server = MockServer::start(vec![
+        http_response("503 Service Unavailable", &[], "unavailable 1"),
+        http_response("503 Service Unavailable", &[], "unavailable 2"),
+        http_response("503 Service Unavailable", &[], "unavailable 3"),
+    ]);
+
+    let error = delete_remote_file(
+        &server.url,
+        "old.csv",
+        "user",
+        "secret",
+        &Client::new(),
+        &fast_retries(1),
+        &quiet(),
+    )
+    .unwrap_err();
+
+    // The first attempt plus one retry, then the last response is reported
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(
+        error.to_string(),
+        "Failed to delete file old.csv: unavailable 2"
+    );
+}
+
+#[test]
+fn test_backoff_doubles() {
+    let retry = RetryPolicy::default();
+
+    assert_eq!(retry.delay(0), Duration::from_millis(500));
+    assert_eq!(retry.delay(1), Duration::from_secs(1));
+    assert_eq!(retry.delay(2), Duration::from_secs(2));
+}
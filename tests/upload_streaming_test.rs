@@ -0,0 +1,47 @@
+//! Tests for streaming the CSV to the DKAN importer endpoint
+
+mod common;
+
+use common::{http_response, MockServer};
+use dkan_importer::output::{Output, Verbosity};
+use dkan_importer::utils::{upload_distribution_csv_file, RetryPolicy};
+use importer_lib::reqwest::blocking::Client;
+
+#[test]
+fn test_csv_is_streamed_with_a_content_length() {
+    let mut csv = String::from("sample_id,temperature\n");
+    for i in 0..20_000 {
+        csv.push_str(&format!("S{i},{}.5\n", i % 30));
+    }
+    let path = std::env::temp_dir().join(format!("streamed_upload_{}.csv", std::process::id()));
+    std::fs::write(&path, &csv).unwrap();
+
+    let server = MockServer::start(vec![http_response(
+        "200 OK",
+        &[("Content-Type", "application/json")],
+        r#"{"data": {"file_url": "https://dkan.example.com/files/samples.csv"}}"#,
+    )]);
+
+    let file_url = upload_distribution_csv_file(
+        &server.url,
+        path.to_str().unwrap(),
+        "samples.csv",
+        "user",
+        "secret",
+        &Client::new(),
+        &RetryPolicy::default(),
+        &Output::stdio(Verbosity::Quiet),
+    )
+    .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(file_url, "https://dkan.example.com/files/samples.csv");
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /api/importer/upload "));
+    // The importer endpoint gets a known length, as with a buffered upload
+    let headers = request.to_lowercase();
+    assert!(headers.contains("content-length: "));
+    assert!(!headers.contains("transfer-encoding: chunked"));
+    assert!(request.contains("filename=\"samples.csv\""));
+    assert!(request.contains(&csv));
+}
@@ -0,0 +1,169 @@
+//! Tests for the on-disk cache of validation results
+
+use dkan_importer::cache::{CachedValidation, ValidationCache, BUILD_VERSIONS};
+use importer_lib::serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn temp_cache_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("{}_{}", name, std::process::id()))
+}
+
+fn mapping() -> HashMap<String, String> {
+    HashMap::from([("Sample ID".to_string(), "sample_id".to_string())])
+}
+
+#[test]
+fn test_second_run_on_identical_inputs_reads_from_cache() {
+    let dir = temp_cache_dir("validation_cache_hit");
+    let cache = ValidationCache::new(&dir);
+    let schema = json!({"type": "object", "properties": {"Sample ID": {"type": "string"}}});
+    let excel_content = b"PK\x03\x04 workbook bytes";
+
+    let key = ValidationCache::key(&schema, &mapping(), excel_content, "Sample");
+    assert!(cache.get(&key).is_none());
+
+    let stored = CachedValidation::new(3);
+    cache.store(&key, &stored).unwrap();
+
+    // Same schema, mapping, bytes and sheet produce the same key and find the stored result
+    let second_key = ValidationCache::key(&schema, &mapping(), excel_content, "Sample");
+    assert_eq!(second_key, key);
+    assert_eq!(cache.get(&second_key), Some(stored));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cache_key_changes_with_any_input() {
+    let schema = json!({"type": "object", "properties": {"Sample ID": {"type": "string"}}});
+    let other_schema = json!({"type": "object", "properties": {"Sample ID": {"type": "integer"}}});
+    let other_mapping = HashMap::from([("Sample ID".to_string(), "id".to_string())]);
+    let key = ValidationCache::key(&schema, &mapping(), b"bytes", "Sample");
+
+    assert_ne!(
+        key,
+        ValidationCache::key(&other_schema, &mapping(), b"bytes", "Sample")
+    );
+    assert_ne!(
+        key,
+        ValidationCache::key(&schema, &other_mapping, b"bytes", "Sample")
+    );
+    assert_ne!(
+        key,
+        ValidationCache::key(&schema, &mapping(), b"other bytes", "Sample")
+    );
+    assert_ne!(
+        key,
+        ValidationCache::key(&schema, &mapping(), b"bytes", "Sheet1")
+    );
+}
+
+#[test]
+fn test_version_change_invalidates_the_entry() {
+    let dir = temp_cache_dir("validation_cache_versions");
+    let cache = ValidationCache::new(&dir);
+    let schema = json!({"type": "object", "properties": {"Sample ID": {"type": "string"}}});
+    let key_for = |versions: &str| {
+        ValidationCache::key_for_versions(versions, &schema, &mapping(), b"bytes", "Sample")
+    };
+
+    let old_key = key_for("dkan-importer 0.1.0, importer-lib 0.1.0");
+    cache.store(&old_key, &CachedValidation::new(3)).unwrap();
+
+    // Results cached before an upgrade of either crate are not found anymore
+    assert!(cache
+        .get(&key_for("dkan-importer 0.2.0, importer-lib 0.1.0"))
+        .is_none());
+    assert!(cache
+        .get(&key_for("dkan-importer 0.1.0, importer-lib 0.2.0"))
+        .is_none());
+    assert!(cache.get(&old_key).is_some());
+    // SHA-256, written in hex
+    assert_eq!(old_key.len(), 64);
+    assert_eq!(
+        ValidationCache::key(&schema, &mapping(), b"bytes", "Sample"),
+        key_for(BUILD_VERSIONS)
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_passing_run_is_cached_with_its_export() {
+    let dir = temp_cache_dir("validation_cache_passed");
+    let cache = ValidationCache::new(&dir);
+    let csv_path = dir.with_extension("csv");
+    std::fs::write(&csv_path, "sample_id\nS1\n").unwrap();
+
+    cache
+        .store_passed("passed", csv_path.to_str().unwrap())
+        .unwrap();
+    let cached = cache.get("passed").unwrap();
+    assert!(cached.passed());
+
+    let restored = dir.with_extension("restored.csv");
+    cache
+        .restore_export("passed", restored.to_str().unwrap())
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&restored).unwrap(),
+        "sample_id\nS1\n"
+    );
+
+    // Without its export, a passing result is useless
+    std::fs::remove_file(dir.join("passed.csv")).unwrap();
+    assert!(cache.get("passed").is_none());
+
+    std::fs::remove_file(&csv_path).unwrap();
+    std::fs::remove_file(&restored).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_expired_entries_are_ignored_and_evicted() {
+    let dir = temp_cache_dir("validation_cache_expired");
+    let cache = ValidationCache::new(&dir);
+    let expired = CachedValidation {
+        stored_at: 0,
+        ..CachedValidation::new(2)
+    };
+    cache.store("old", &expired).unwrap();
+    std::fs::write(dir.join("old.csv"), "sample_id\nS1\n").unwrap();
+    // An entry written by an older version, which kept the error log
+    std::fs::write(
+        dir.join("legacy.json"),
+        r#"{"error_count": 1, "errors_log": "Row data: ...", "created_at": "2024-01-01"}"#,
+    )
+    .unwrap();
+
+    assert!(cache.get("old").is_none());
+    cache.store("new", &CachedValidation::new(1)).unwrap();
+
+    assert!(!dir.join("old.json").exists());
+    assert!(!dir.join("old.csv").exists());
+    assert!(!dir.join("legacy.json").exists());
+    assert_eq!(cache.get("new").map(|cached| cached.error_count), Some(1));
+
+    // With a zero time to live, nothing is ever used
+    let no_ttl = ValidationCache::new(&dir).with_ttl(Duration::ZERO);
+    assert!(no_ttl.get("new").is_none());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_cached_failure_keeps_no_row_data() {
+    let dir = temp_cache_dir("validation_cache_no_rows");
+    let cache = ValidationCache::new(&dir);
+    cache.store("failed", &CachedValidation::new(4)).unwrap();
+
+    let content = std::fs::read_to_string(dir.join("failed.json")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let stored: importer_lib::serde_json::Value =
+        importer_lib::serde_json::from_str(&content).unwrap();
+    let mut fields: Vec<&String> = stored.as_object().unwrap().keys().collect();
+    fields.sort();
+    assert_eq!(fields, vec!["created_at", "error_count", "stored_at"]);
+}